@@ -0,0 +1,94 @@
+use crate::error::A2AResult;
+use crate::store::TaskStore;
+use crate::types::{Artifact, Message, Part, TaskState, TaskStatus, TextPart};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Business logic for turning an incoming message into task progress.
+///
+/// Implementations drive `store` directly: setting intermediate `Working`
+/// or `InputRequired` states and appending artifacts as they go, the same
+/// way a human operator would via `tasks/get`. Because every store
+/// mutation is broadcast, a `tasks/sendSubscribe` caller sees each step as
+/// it happens without the agent needing to know anything about SSE.
+#[async_trait]
+pub trait Agent: Send + Sync {
+    async fn handle(
+        &self,
+        task_id: &str,
+        message: Message,
+        store: &Arc<dyn TaskStore>,
+    ) -> A2AResult<()>;
+}
+
+/// The reference agent used when a caller doesn't plug in its own: echoes
+/// the incoming message back as a single text artifact.
+pub struct EchoAgent;
+
+#[async_trait]
+impl Agent for EchoAgent {
+    async fn handle(
+        &self,
+        task_id: &str,
+        message: Message,
+        store: &Arc<dyn TaskStore>,
+    ) -> A2AResult<()> {
+        store.update_task_status(
+            task_id,
+            TaskStatus {
+                state: TaskState::Working,
+                message: None,
+                timestamp: Utc::now(),
+            },
+        )?;
+
+        let text = message
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Text(text_part) => Some(text_part.text.clone()),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let response_message = Message {
+            role: "agent".to_string(),
+            parts: vec![Part::Text(TextPart {
+                part_type: "text".to_string(),
+                text: format!("Rust A2A server received: {}", text),
+                metadata: None,
+            })],
+            metadata: None,
+        };
+
+        store.add_artifact(
+            task_id,
+            Artifact {
+                name: Some("result".to_string()),
+                description: Some("Task result".to_string()),
+                parts: vec![Part::Text(TextPart {
+                    part_type: "text".to_string(),
+                    text: "This is a sample artifact from the Rust A2A server.".to_string(),
+                    metadata: None,
+                })],
+                index: 0,
+                append: None,
+                metadata: None,
+                last_chunk: Some(true),
+            },
+        )?;
+
+        store.update_task_status(
+            task_id,
+            TaskStatus {
+                state: TaskState::Completed,
+                message: Some(response_message),
+                timestamp: Utc::now(),
+            },
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,326 @@
+mod protocol;
+mod registry;
+
+pub use protocol::{DispatcherMessage, RunnerMessage, TaskInfo};
+pub use registry::{RunnerInfo, RunnerRegistry};
+
+use crate::agent::Agent;
+use crate::error::{A2AError, A2AResult};
+use crate::store::TaskStore;
+use crate::types::{Message, Part, TaskState, TaskStatus, TextPart};
+use async_trait::async_trait;
+use axum::{
+    extract::State,
+    routing::post,
+    Json, Router,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An `Agent` for CPU-heavy or sandboxed work that shouldn't run inline in
+/// the server's own worker pool: instead of doing the work itself, it hands
+/// the task to whichever connected runner process is idle and advertises
+/// the right skill, then returns immediately. The runner streams its own
+/// progress back to `runner_routes`, which writes it into `TaskStore`
+/// directly — `handle` itself never sees that progress.
+///
+/// A message's `skill` metadata field (if set) picks which runner gets the
+/// task; without one, any idle runner is eligible.
+pub struct DispatchAgent {
+    registry: Arc<RunnerRegistry>,
+}
+
+impl DispatchAgent {
+    /// Build a `DispatchAgent` and the `RunnerRegistry` it shares with
+    /// `runner_routes` — mount both on the same server so runners connecting
+    /// to `/runner/*` feed tasks assigned here.
+    pub fn new() -> (Self, Arc<RunnerRegistry>) {
+        let registry = Arc::new(RunnerRegistry::new());
+        (
+            Self {
+                registry: registry.clone(),
+            },
+            registry,
+        )
+    }
+}
+
+#[async_trait]
+impl Agent for DispatchAgent {
+    async fn handle(
+        &self,
+        task_id: &str,
+        message: Message,
+        store: &Arc<dyn TaskStore>,
+    ) -> A2AResult<()> {
+        let skill = message
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("skill"))
+            .and_then(|value| value.as_str())
+            .map(str::to_string);
+
+        let task = TaskInfo {
+            task_id: task_id.to_string(),
+            message,
+        };
+
+        self.registry.assign(task, skill.as_deref()).ok_or_else(|| {
+            A2AError::InternalServerError(
+                "no connected runner is available to take this task".to_string(),
+            )
+        })?;
+
+        store.update_task_status(
+            task_id,
+            TaskStatus {
+                state: TaskState::Working,
+                message: None,
+                timestamp: Utc::now(),
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+fn failure_message(reason: &str) -> Message {
+    Message {
+        role: "agent".to_string(),
+        parts: vec![Part::Text(TextPart {
+            part_type: "text".to_string(),
+            text: reason.to_string(),
+            metadata: None,
+        })],
+        metadata: None,
+    }
+}
+
+/// Shared state for the runner-facing routes, distinct from the JSON-RPC
+/// `AppState` in `server.rs` since runners speak their own protocol.
+struct RunnerState {
+    registry: Arc<RunnerRegistry>,
+    task_store: Arc<dyn TaskStore>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectRequest {
+    host: String,
+    skills: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConnectResponse {
+    runner_id: String,
+}
+
+/// A runner's first call: report its host and the skills it can execute.
+async fn connect(
+    State(state): State<Arc<RunnerState>>,
+    Json(request): Json<ConnectRequest>,
+) -> Json<ConnectResponse> {
+    let runner_id = state.registry.connect(request.host, request.skills);
+    Json(ConnectResponse { runner_id })
+}
+
+#[derive(Debug, Deserialize)]
+struct PollRequest {
+    runner_id: String,
+}
+
+/// Long-poll for the next task. Resolves as soon as `DispatchAgent::handle`
+/// assigns this runner one.
+async fn poll(
+    State(state): State<Arc<RunnerState>>,
+    Json(request): Json<PollRequest>,
+) -> A2AResult<Json<DispatcherMessage>> {
+    let receiver = state.registry.wait_for_work(&request.runner_id).ok_or_else(|| {
+        A2AError::InvalidRequest(format!("unknown runner {}", request.runner_id))
+    })?;
+
+    match receiver.await {
+        Ok(message) => Ok(Json(message)),
+        Err(_) => Err(A2AError::InvalidRequest(
+            "runner was forgotten by the dispatcher; reconnect".to_string(),
+        )),
+    }
+}
+
+/// Relay a runner's status for the task it was assigned into `TaskStore`,
+/// the same way `EchoAgent`/`OpenAIAgent` drive the store directly.
+async fn report(
+    State(state): State<Arc<RunnerState>>,
+    Json(message): Json<RunnerMessage>,
+) -> A2AResult<()> {
+    match message {
+        RunnerMessage::ArtifactChunk { task_id, artifact } => {
+            state.task_store.add_artifact(&task_id, artifact)?;
+        }
+        RunnerMessage::Completed { task_id, message } => {
+            state.registry.complete(&task_id);
+            state.task_store.update_task_status(
+                &task_id,
+                TaskStatus {
+                    state: TaskState::Completed,
+                    message,
+                    timestamp: Utc::now(),
+                },
+            )?;
+        }
+        RunnerMessage::Failed { task_id, reason } => {
+            state.registry.complete(&task_id);
+            state.task_store.update_task_status(
+                &task_id,
+                TaskStatus {
+                    state: TaskState::Failed,
+                    message: Some(failure_message(&reason)),
+                    timestamp: Utc::now(),
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct DisconnectRequest {
+    runner_id: String,
+}
+
+/// A runner's last call before shutting down (or a supervisor's call on its
+/// behalf once it notices the runner is gone). If it was mid-task, try
+/// handing that task to another idle runner; fail the task outright if none
+/// is available.
+async fn disconnect(
+    State(state): State<Arc<RunnerState>>,
+    Json(request): Json<DisconnectRequest>,
+) -> A2AResult<()> {
+    let Some((task, skill)) = state.registry.disconnect(&request.runner_id) else {
+        return Ok(());
+    };
+
+    reassign_or_fail(&state, task, skill).await
+}
+
+/// Hand a task whose runner is no longer around to another idle runner, or
+/// fail it outright if none is available. Shared by `disconnect` (a runner's
+/// clean goodbye) and the `sweep_expired` loop (a runner that went silent
+/// without saying one).
+async fn reassign_or_fail(
+    state: &RunnerState,
+    task: TaskInfo,
+    skill: Option<String>,
+) -> A2AResult<()> {
+    let task_id = task.task_id.clone();
+    if state.registry.assign(task, skill.as_deref()).is_none() {
+        state.task_store.update_task_status(
+            &task_id,
+            TaskStatus {
+                state: TaskState::Failed,
+                message: Some(failure_message(
+                    "the runner working this task hung up and no other runner was available",
+                )),
+                timestamp: Utc::now(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct HeartbeatRequest {
+    runner_id: String,
+}
+
+/// A runner's keep-alive, sent periodically (including while it's mid-task
+/// and not otherwise calling in) so `sweep_expired` doesn't mistake it for
+/// hung. An error here means the dispatcher already forgot this runner,
+/// e.g. it was already swept — the runner should reconnect.
+async fn heartbeat(
+    State(state): State<Arc<RunnerState>>,
+    Json(request): Json<HeartbeatRequest>,
+) -> A2AResult<()> {
+    if state.registry.heartbeat(&request.runner_id) {
+        Ok(())
+    } else {
+        Err(A2AError::InvalidRequest(format!(
+            "unknown runner {}",
+            request.runner_id
+        )))
+    }
+}
+
+/// How long a runner can go without a heartbeat before `sweep_loop` treats
+/// it as hung and reassigns/fails whatever it was working.
+const RUNNER_LIVENESS_TIMEOUT: Duration = Duration::from_secs(30);
+/// How often `sweep_loop` checks for expired runners.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Background task that periodically forgets runners that have gone silent
+/// for longer than `RUNNER_LIVENESS_TIMEOUT`, reassigning (or failing)
+/// whatever task each was working — the same outcome an explicit
+/// `/runner/disconnect` call produces, for runners that never get to make
+/// that call.
+async fn sweep_loop(state: Arc<RunnerState>) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        for (task, skill) in state.registry.sweep_expired(RUNNER_LIVENESS_TIMEOUT) {
+            if let Err(err) = reassign_or_fail(&state, task, skill).await {
+                tracing::warn!("failed to reassign a hung runner's task: {}", err);
+            }
+        }
+    }
+}
+
+async fn list_runners(State(state): State<Arc<RunnerState>>) -> Json<Vec<RunnerSummary>> {
+    Json(
+        state
+            .registry
+            .list()
+            .into_iter()
+            .map(|runner| RunnerSummary {
+                id: runner.id,
+                host: runner.host,
+                skills: runner.skills,
+                assigned_task: runner.assigned_task,
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct RunnerSummary {
+    id: String,
+    host: String,
+    skills: Vec<String>,
+    assigned_task: Option<String>,
+}
+
+/// Routes a runner process talks to: `connect` to register, `poll` to
+/// long-poll for the next task, `report` to stream progress on whatever
+/// it's working, `heartbeat` to prove it's still alive while mid-task, and
+/// `disconnect` on clean shutdown. `GET /runner/list` is a read-only status
+/// view over the same registry. Also spawns `sweep_loop` in the background,
+/// so a runner that hangs without ever calling `disconnect` still gets its
+/// task reassigned. Merge this into the server's main router alongside
+/// `create_router_with_store` — both need the same `task_store` and the
+/// `RunnerRegistry` `DispatchAgent::new` returned.
+pub fn runner_routes(registry: Arc<RunnerRegistry>, task_store: Arc<dyn TaskStore>) -> Router {
+    let state = Arc::new(RunnerState { registry, task_store });
+
+    tokio::spawn(sweep_loop(state.clone()));
+
+    Router::new()
+        .route("/runner/connect", post(connect))
+        .route("/runner/poll", post(poll))
+        .route("/runner/report", post(report))
+        .route("/runner/heartbeat", post(heartbeat))
+        .route("/runner/disconnect", post(disconnect))
+        .route("/runner/list", axum::routing::get(list_runners))
+        .with_state(state)
+}
@@ -0,0 +1,30 @@
+use crate::types::{Artifact, Message};
+use serde::{Deserialize, Serialize};
+
+/// Everything a runner needs to start work on a task, handed back from
+/// `/runner/poll` once the dispatcher has assigned one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInfo {
+    pub task_id: String,
+    pub message: Message,
+}
+
+/// What a runner's long-poll against `/runner/poll` resolves to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DispatcherMessage {
+    /// A task is ready; the runner should start work on it immediately.
+    NewTaskPlease(TaskInfo),
+    /// Nothing is waiting; the runner should poll again.
+    NoWork,
+}
+
+/// Progress a runner reports back to `/runner/report` for the task it was
+/// handed. `handle_report` relays these straight into `TaskStore`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RunnerMessage {
+    ArtifactChunk { task_id: String, artifact: Artifact },
+    Completed { task_id: String, message: Option<Message> },
+    Failed { task_id: String, reason: String },
+}
@@ -0,0 +1,317 @@
+use super::protocol::{DispatcherMessage, TaskInfo};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+pub type RunnerId = String;
+
+/// A connected runner. `reply` holds the long-poll response channel while
+/// the runner is idle, and is taken once a task is assigned to it.
+/// `last_seen` is bumped on every connect/heartbeat/report and is how
+/// `sweep_expired` notices a runner that crashed or lost its connection
+/// without calling `/runner/disconnect`.
+struct RunnerHandle {
+    host: String,
+    skills: Vec<String>,
+    reply: Option<oneshot::Sender<DispatcherMessage>>,
+    assigned_task: Option<String>,
+    last_seen: Instant,
+}
+
+/// The task and skill a runner was assigned, kept around so a hangup can
+/// be handed to a different runner instead of just failing outright.
+struct InFlight {
+    task: TaskInfo,
+    skill: Option<String>,
+}
+
+/// Host/capability info for a connected runner, as reported at connect
+/// time; returned by `list` for status/debugging.
+pub struct RunnerInfo {
+    pub id: RunnerId,
+    pub host: String,
+    pub skills: Vec<String>,
+    pub assigned_task: Option<String>,
+}
+
+/// Live runner connections and the in-flight task each is working, mirroring
+/// the `active_tasks` map `server.rs` keeps for cancellation. A task's
+/// `RunnerId` is tracked in `assignments` so a status/artifact report can
+/// find its runner (or a hangup can find its task) without scanning
+/// `runners`.
+#[derive(Default)]
+pub struct RunnerRegistry {
+    runners: Mutex<HashMap<RunnerId, RunnerHandle>>,
+    assignments: Mutex<HashMap<String, RunnerId>>,
+    in_flight: Mutex<HashMap<String, InFlight>>,
+}
+
+impl RunnerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a newly connected runner, advertising its host and skills,
+    /// and return the id it should use on `/runner/poll` and
+    /// `/runner/report`.
+    pub fn connect(&self, host: String, skills: Vec<String>) -> RunnerId {
+        let id = Uuid::new_v4().to_string();
+        self.runners.lock().unwrap().insert(
+            id.clone(),
+            RunnerHandle {
+                host,
+                skills,
+                reply: None,
+                assigned_task: None,
+                last_seen: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Record that a runner is still alive, e.g. from `/runner/heartbeat` or
+    /// any other call it makes. Returns whether `runner_id` is still
+    /// registered (a runner that's been swept as expired should reconnect).
+    pub fn heartbeat(&self, runner_id: &str) -> bool {
+        match self.runners.lock().unwrap().get_mut(runner_id) {
+            Some(handle) => {
+                handle.last_seen = Instant::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget every runner that hasn't been heard from (connect, heartbeat,
+    /// or report) within `timeout`, the same way `disconnect` forgets one
+    /// explicitly. Returns the task (and skill) each expired runner was
+    /// mid-way through, if any, so the caller can reassign them.
+    pub fn sweep_expired(&self, timeout: Duration) -> Vec<(TaskInfo, Option<String>)> {
+        let expired: Vec<RunnerId> = {
+            let runners = self.runners.lock().unwrap();
+            runners
+                .iter()
+                .filter(|(_, handle)| handle.last_seen.elapsed() > timeout)
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+
+        expired
+            .iter()
+            .filter_map(|runner_id| self.disconnect(runner_id))
+            .collect()
+    }
+
+    /// Forget a runner, e.g. because it hung up. Returns the task (and the
+    /// skill it was routed on) it was mid-way through, if any, so the
+    /// caller can reassign it to a different runner.
+    pub fn disconnect(&self, runner_id: &str) -> Option<(TaskInfo, Option<String>)> {
+        let task_id = {
+            let mut runners = self.runners.lock().unwrap();
+            runners.remove(runner_id)?.assigned_task
+        }?;
+        self.assignments.lock().unwrap().remove(&task_id);
+        let in_flight = self.in_flight.lock().unwrap().remove(&task_id)?;
+        Some((in_flight.task, in_flight.skill))
+    }
+
+    /// Long-poll for work: park a reply channel on the runner's handle and
+    /// return the receiving half, which resolves once `assign` hands it a
+    /// task. `None` means `runner_id` isn't registered (e.g. the dispatcher
+    /// restarted); the caller should tell the runner to reconnect.
+    pub fn wait_for_work(&self, runner_id: &str) -> Option<oneshot::Receiver<DispatcherMessage>> {
+        let mut runners = self.runners.lock().unwrap();
+        let handle = runners.get_mut(runner_id)?;
+        let (tx, rx) = oneshot::channel();
+        handle.reply = Some(tx);
+        handle.last_seen = Instant::now();
+        Some(rx)
+    }
+
+    /// Find an idle runner — one currently parked in `wait_for_work` — that
+    /// advertises `skill` (any idle runner if `skill` is `None`) and hand it
+    /// `task`. Returns the chosen runner's id, or `None` if no runner
+    /// matched.
+    pub fn assign(&self, task: TaskInfo, skill: Option<&str>) -> Option<RunnerId> {
+        let mut runners = self.runners.lock().unwrap();
+        let (runner_id, handle) = runners.iter_mut().find(|(_, handle)| {
+            handle.reply.is_some()
+                && skill.map_or(true, |skill| handle.skills.iter().any(|s| s == skill))
+        })?;
+        let runner_id = runner_id.clone();
+        let reply = handle.reply.take()?;
+        handle.assigned_task = Some(task.task_id.clone());
+
+        self.assignments
+            .lock()
+            .unwrap()
+            .insert(task.task_id.clone(), runner_id.clone());
+        self.in_flight.lock().unwrap().insert(
+            task.task_id.clone(),
+            InFlight {
+                task: task.clone(),
+                skill: skill.map(str::to_string),
+            },
+        );
+
+        let _ = reply.send(DispatcherMessage::NewTaskPlease(task));
+        Some(runner_id)
+    }
+
+    /// Clear a task's assignment once it reaches a terminal state
+    /// (`Completed` or `Failed`).
+    pub fn complete(&self, task_id: &str) {
+        self.in_flight.lock().unwrap().remove(task_id);
+        let runner_id = self.assignments.lock().unwrap().remove(task_id);
+        if let Some(runner_id) = runner_id {
+            if let Some(handle) = self.runners.lock().unwrap().get_mut(&runner_id) {
+                handle.assigned_task = None;
+            }
+        }
+    }
+
+    /// Snapshot of every connected runner, for a status endpoint.
+    pub fn list(&self) -> Vec<RunnerInfo> {
+        self.runners
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, handle)| RunnerInfo {
+                id: id.clone(),
+                host: handle.host.clone(),
+                skills: handle.skills.clone(),
+                assigned_task: handle.assigned_task.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Message;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn sample_task(task_id: &str) -> TaskInfo {
+        TaskInfo {
+            task_id: task_id.to_string(),
+            message: Message {
+                role: "user".to_string(),
+                parts: vec![],
+                metadata: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_assign_routes_to_the_runner_advertising_the_skill() {
+        let registry = RunnerRegistry::new();
+        let general = registry.connect("general-host".to_string(), vec!["general".to_string()]);
+        let gpu = registry.connect("gpu-host".to_string(), vec!["gpu".to_string()]);
+
+        let mut general_rx = registry.wait_for_work(&general).unwrap();
+        let mut gpu_rx = registry.wait_for_work(&gpu).unwrap();
+
+        let assigned = registry.assign(sample_task("t1"), Some("gpu")).unwrap();
+        assert_eq!(assigned, gpu);
+
+        let message = gpu_rx
+            .try_recv()
+            .expect("the gpu runner should have been handed the task");
+        assert!(matches!(message, DispatcherMessage::NewTaskPlease(_)));
+        assert!(
+            general_rx.try_recv().is_err(),
+            "the general runner should still be idle"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_mid_task_can_be_reassigned() {
+        let registry = RunnerRegistry::new();
+        let a = registry.connect("a-host".to_string(), vec![]);
+        let _a_rx = registry.wait_for_work(&a).unwrap();
+        registry.assign(sample_task("t1"), None).unwrap();
+
+        let b = registry.connect("b-host".to_string(), vec![]);
+        let mut b_rx = registry.wait_for_work(&b).unwrap();
+
+        let (task, skill) = registry.disconnect(&a).expect("a was working a task");
+        assert_eq!(task.task_id, "t1");
+
+        let reassigned = registry.assign(task, skill.as_deref()).unwrap();
+        assert_eq!(reassigned, b);
+        assert!(b_rx.try_recv().is_ok());
+    }
+
+    // Regression test for a lock-order bug where `complete()` held the
+    // `assignments` guard for its whole `if let` body while nesting a
+    // `runners` lock underneath it, the reverse of the order `assign()`
+    // takes — a task submission racing a runner's completion report could
+    // deadlock both permanently.
+    #[tokio::test]
+    async fn test_assign_and_complete_do_not_deadlock_under_concurrency() {
+        let registry = Arc::new(RunnerRegistry::new());
+
+        let result = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut handles = Vec::new();
+            for i in 0..50 {
+                let registry = registry.clone();
+                handles.push(tokio::spawn(async move {
+                    let runner_id = registry.connect(format!("host-{i}"), vec![]);
+                    let rx = registry.wait_for_work(&runner_id).unwrap();
+                    registry.assign(sample_task(&format!("task-{i}")), None);
+                    let _ = rx.await;
+                    registry.complete(&format!("task-{i}"));
+                }));
+            }
+            for handle in handles {
+                handle.await.unwrap();
+            }
+        })
+        .await;
+
+        assert!(result.is_ok(), "assign/complete deadlocked under concurrency");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expired_reassigns_a_hung_runners_task() {
+        let registry = RunnerRegistry::new();
+        let a = registry.connect("a-host".to_string(), vec![]);
+        let _a_rx = registry.wait_for_work(&a).unwrap();
+        registry.assign(sample_task("t1"), None).unwrap();
+
+        let b = registry.connect("b-host".to_string(), vec![]);
+        let mut b_rx = registry.wait_for_work(&b).unwrap();
+
+        // `a` never calls /runner/heartbeat or /runner/disconnect again —
+        // simulate it going silent by sweeping with a zero timeout.
+        let expired = registry.sweep_expired(Duration::from_secs(0));
+        assert_eq!(expired.len(), 1);
+
+        let (task, skill) = expired.into_iter().next().unwrap();
+        assert_eq!(task.task_id, "t1");
+
+        let reassigned = registry.assign(task, skill.as_deref()).unwrap();
+        assert_eq!(reassigned, b);
+        assert!(b_rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_keeps_a_runner_from_being_swept() {
+        let registry = RunnerRegistry::new();
+        let a = registry.connect("a-host".to_string(), vec![]);
+
+        assert!(registry.heartbeat(&a));
+        assert!(
+            registry.sweep_expired(Duration::from_secs(60)).is_empty(),
+            "a recent heartbeat should keep the runner alive"
+        );
+        assert!(
+            !registry.heartbeat("not-a-runner"),
+            "heartbeat on an unknown runner should report false"
+        );
+    }
+}
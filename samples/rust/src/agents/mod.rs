@@ -0,0 +1,2 @@
+pub mod dispatch_agent;
+pub mod openai_agent;
@@ -0,0 +1,127 @@
+use crate::agent::Agent;
+use crate::error::{A2AError, A2AResult};
+use crate::store::TaskStore;
+use crate::types::{Artifact, Message, Part, TaskState, TaskStatus, TextPart};
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use std::sync::Arc;
+
+const CHAT_COMPLETIONS_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_MODEL: &str = "gpt-3.5-turbo";
+
+/// An `Agent` that answers by forwarding the task's message to OpenAI's
+/// chat completions endpoint.
+pub struct OpenAIAgent {
+    http_client: Client,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAIAgent {
+    /// Read `OPENAI_API_KEY` (required) and `OPENAI_MODEL` (optional,
+    /// defaults to `gpt-3.5-turbo`) from the environment.
+    pub fn new() -> anyhow::Result<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .map_err(|_| anyhow::anyhow!("OPENAI_API_KEY environment variable is not set"))?;
+        let model = std::env::var("OPENAI_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+
+        Ok(Self {
+            http_client: Client::new(),
+            api_key,
+            model,
+        })
+    }
+}
+
+#[async_trait]
+impl Agent for OpenAIAgent {
+    async fn handle(
+        &self,
+        task_id: &str,
+        message: Message,
+        store: &Arc<dyn TaskStore>,
+    ) -> A2AResult<()> {
+        store.update_task_status(
+            task_id,
+            TaskStatus {
+                state: TaskState::Working,
+                message: None,
+                timestamp: Utc::now(),
+            },
+        )?;
+
+        let prompt = message
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Text(text_part) => Some(text_part.text.clone()),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let body = serde_json::json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+
+        let response: serde_json::Value = self
+            .http_client
+            .post(CHAT_COMPLETIONS_URL)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let reply = response["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| {
+                A2AError::InternalServerError(format!(
+                    "unexpected OpenAI response: {}",
+                    response
+                ))
+            })?
+            .to_string();
+
+        let response_message = Message {
+            role: "agent".to_string(),
+            parts: vec![Part::Text(TextPart {
+                part_type: "text".to_string(),
+                text: reply.clone(),
+                metadata: None,
+            })],
+            metadata: None,
+        };
+
+        store.add_artifact(
+            task_id,
+            Artifact {
+                name: Some("result".to_string()),
+                description: Some("OpenAI response".to_string()),
+                parts: vec![Part::Text(TextPart {
+                    part_type: "text".to_string(),
+                    text: reply,
+                    metadata: None,
+                })],
+                index: 0,
+                append: None,
+                metadata: None,
+                last_chunk: Some(true),
+            },
+        )?;
+
+        store.update_task_status(
+            task_id,
+            TaskStatus {
+                state: TaskState::Completed,
+                message: Some(response_message),
+                timestamp: Utc::now(),
+            },
+        )?;
+
+        Ok(())
+    }
+}
@@ -47,7 +47,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             break;
         }
         
-        match client.send_task(input).await {
+        match client.send_task_and_wait(input).await {
             Ok(task) => {
                 if let Some(message) = &task.status.message {
                     for part in &message.parts {
@@ -0,0 +1,59 @@
+use a2a_rust::agents::dispatch_agent::{runner_routes, DispatchAgent};
+use a2a_rust::server::{create_router_with_store, DEFAULT_WORKER_COUNT};
+use a2a_rust::store::MemoryTaskStore;
+use a2a_rust::AuthConfig;
+use clap::Parser;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Host address to bind to
+    #[arg(short, long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 3000)]
+    port: u16,
+
+    /// Number of background workers handing submitted tasks off to runners
+    #[arg(short, long, default_value_t = DEFAULT_WORKER_COUNT)]
+    workers: usize,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
+        ))
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let args = Args::parse();
+    let addr = format!("{}:{}", args.host, args.port)
+        .parse::<SocketAddr>()
+        .expect("Invalid address");
+
+    // An `AUTH_SECRET` env var locks the JSON-RPC side down to callers that
+    // send a matching bearer token; it doesn't gate `/runner/*`, since
+    // runners authenticate by being handed the dispatcher's address out of
+    // band (the same trust boundary a CI driver/runner pair assumes).
+    let auth = std::env::var("AUTH_SECRET")
+        .ok()
+        .map(|token| AuthConfig { token });
+
+    let task_store = Arc::new(MemoryTaskStore::new());
+    let (agent, registry) = DispatchAgent::new();
+
+    let app = create_router_with_store(task_store.clone(), args.workers, auth, None, agent)
+        .merge(runner_routes(registry, task_store));
+
+    tracing::info!("Starting A2A dispatcher on {}", addr);
+    tracing::info!("Runners should connect to /runner/connect and long-poll /runner/poll");
+    axum::serve(tokio::net::TcpListener::bind(&addr).await?, app).await?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use a2a_rust::agents::openai_agent::{OpenAIAgent, server::create_router};
+use a2a_rust::agents::openai_agent::OpenAIAgent;
+use a2a_rust::server::DEFAULT_WORKER_COUNT;
+use a2a_rust::{create_router, AuthConfig};
 use clap::Parser;
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -13,6 +15,10 @@ struct Args {
     /// Port to listen on
     #[arg(short, long, default_value_t = 3000)]
     port: u16,
+
+    /// Number of background workers processing submitted tasks
+    #[arg(short, long, default_value_t = DEFAULT_WORKER_COUNT)]
+    workers: usize,
 }
 
 #[tokio::main]
@@ -33,9 +39,15 @@ async fn main() -> anyhow::Result<()> {
 
     // Create the OpenAI agent
     let agent = OpenAIAgent::new()?;
-    
+
+    // An `AUTH_SECRET` env var locks the server down to callers that send a
+    // matching bearer token; unset, it stays open.
+    let auth = std::env::var("AUTH_SECRET")
+        .ok()
+        .map(|token| AuthConfig { token });
+
     // Create the router
-    let app = create_router(agent);
+    let app = create_router(args.workers, auth, None, agent);
 
     // Start the server
     println!("Starting OpenAI A2A agent on {}", addr);
@@ -1,6 +1,9 @@
-use a2a_rust::create_router;
+use a2a_rust::server::{create_router_with_store, DEFAULT_WORKER_COUNT};
+use a2a_rust::store::{MemoryTaskStore, SqliteTaskStore, TaskStore};
+use a2a_rust::{AuthConfig, EchoAgent};
 use clap::Parser;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Parser, Debug)]
@@ -13,6 +16,15 @@ struct Args {
     /// Port to listen on
     #[arg(short, long, default_value_t = 3000)]
     port: u16,
+
+    /// Number of background workers processing submitted tasks
+    #[arg(short, long, default_value_t = DEFAULT_WORKER_COUNT)]
+    workers: usize,
+
+    /// Path to a SQLite database file to persist tasks in; omit to use the
+    /// default in-memory store (tasks are lost on restart)
+    #[arg(long)]
+    db: Option<String>,
 }
 
 #[tokio::main]
@@ -31,11 +43,25 @@ async fn main() {
         .parse::<SocketAddr>()
         .expect("Invalid address");
 
+    // An `AUTH_SECRET` env var locks the server down to callers that send a
+    // matching bearer token; unset, it stays open like before.
+    let auth = std::env::var("AUTH_SECRET")
+        .ok()
+        .map(|token| AuthConfig { token });
+
+    let task_store: Arc<dyn TaskStore> = match &args.db {
+        Some(path) => Arc::new(SqliteTaskStore::open(path).expect("failed to open SQLite database")),
+        None => Arc::new(MemoryTaskStore::new()),
+    };
+
     // Create the router
-    let app = create_router();
+    let app = create_router_with_store(task_store, args.workers, auth, None, EchoAgent);
 
     // Start the server
-    tracing::info!("Starting A2A server on {}", addr);
+    match &args.db {
+        Some(path) => tracing::info!("Starting A2A server on {} (tasks persisted to {})", addr, path),
+        None => tracing::info!("Starting A2A server on {} (in-memory store)", addr),
+    }
     axum::serve(tokio::net::TcpListener::bind(&addr).await.unwrap(), app)
         .await
         .unwrap();
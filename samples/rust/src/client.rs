@@ -1,16 +1,24 @@
 use crate::error::{A2AError, A2AResult};
 use crate::types::{
-    CancelTaskRequest, GetTaskRequest, JsonRpcResponse, Message, Part, SendTaskRequest, Task,
-    TaskIdParams, TaskQueryParams, TaskSendParams, TextPart,
+    AgentCard, CancelTaskRequest, GetTaskRequest, JsonRpcResponse, Message, Part, RequestId,
+    SendTaskRequest, Task, TaskIdParams, TaskQueryParams, TaskSendParams, TextPart,
 };
 use reqwest::Client as HttpClient;
 use serde::de::DeserializeOwned;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// How often `wait_for_completion` polls `tasks/get`.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// How many times `wait_for_completion` polls before giving up (~1 minute
+/// total at `POLL_INTERVAL`).
+const MAX_POLLS: u32 = 200;
+
 /// A client for interacting with an A2A server
 pub struct A2AClient {
     http_client: HttpClient,
     base_url: String,
+    token: Option<String>,
 }
 
 impl A2AClient {
@@ -18,20 +26,28 @@ impl A2AClient {
         Self {
             http_client: HttpClient::new(),
             base_url: base_url.to_string(),
+            token: None,
         }
     }
 
+    /// Send `Authorization: Bearer <token>` with every request, for servers
+    /// started with an `AuthConfig`.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
     async fn send_request<T, R>(&self, request: T) -> A2AResult<R>
     where
         T: serde::Serialize,
         R: DeserializeOwned,
     {
-        let response = self
-            .http_client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await?;
+        let mut request_builder = self.http_client.post(&self.base_url).json(&request);
+        if let Some(token) = &self.token {
+            request_builder = request_builder.bearer_auth(token);
+        }
+
+        let response = request_builder.send().await?;
 
         if !response.status().is_success() {
             return Err(A2AError::InvalidRequest(format!(
@@ -63,7 +79,7 @@ impl A2AClient {
 
         let request = SendTaskRequest {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/send".to_string(),
             params: TaskSendParams {
                 id: task_id,
@@ -81,11 +97,38 @@ impl A2AClient {
         self.send_request::<SendTaskRequest, Task>(request).await
     }
 
+    /// Send a message and poll `tasks/get` until the task leaves it
+    /// non-terminal state, since `tasks/send` itself returns as soon as the
+    /// task is queued (typically still `Submitted`, with no agent message
+    /// yet). Callers that want the queued task back immediately should use
+    /// `send_task` directly.
+    pub async fn send_task_and_wait(&self, message: &str) -> A2AResult<Task> {
+        let task = self.send_task(message).await?;
+        self.wait_for_completion(&task.id).await
+    }
+
+    /// Poll `tasks/get` until `task_id` reaches a terminal state, or give up
+    /// after `MAX_POLLS` attempts.
+    pub async fn wait_for_completion(&self, task_id: &str) -> A2AResult<Task> {
+        for _ in 0..MAX_POLLS {
+            let task = self.get_task(task_id).await?;
+            if task.status.state.is_terminal() {
+                return Ok(task);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        Err(A2AError::InvalidRequest(format!(
+            "timed out waiting for task {} to finish",
+            task_id
+        )))
+    }
+
     /// Get a task by ID
     pub async fn get_task(&self, task_id: &str) -> A2AResult<Task> {
         let request = GetTaskRequest {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/get".to_string(),
             params: TaskQueryParams {
                 id: task_id.to_string(),
@@ -101,7 +144,7 @@ impl A2AClient {
     pub async fn cancel_task(&self, task_id: &str) -> A2AResult<Task> {
         let request = CancelTaskRequest {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/cancel".to_string(),
             params: TaskIdParams {
                 id: task_id.to_string(),
@@ -111,4 +154,29 @@ impl A2AClient {
 
         self.send_request::<CancelTaskRequest, Task>(request).await
     }
+
+    /// Fetch the server's `AgentCard` from `/.well-known/agent.json`, so
+    /// callers can discover its capabilities before sending work.
+    pub async fn get_agent_card(&self) -> A2AResult<AgentCard> {
+        let url = format!(
+            "{}/.well-known/agent.json",
+            self.base_url.trim_end_matches('/')
+        );
+
+        let mut request_builder = self.http_client.get(&url);
+        if let Some(token) = &self.token {
+            request_builder = request_builder.bearer_auth(token);
+        }
+
+        let response = request_builder.send().await?;
+
+        if !response.status().is_success() {
+            return Err(A2AError::InvalidRequest(format!(
+                "HTTP error: {}",
+                response.status()
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
 }
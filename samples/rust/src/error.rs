@@ -25,6 +25,12 @@ pub enum A2AError {
 
     #[error("Unsupported operation: {0}")]
     UnsupportedOperation(String),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] rusqlite::Error),
+
+    #[error("Unauthorized")]
+    Unauthorized,
 }
 
 pub type A2AResult<T> = Result<T, A2AError>;
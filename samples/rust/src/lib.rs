@@ -1,12 +1,16 @@
+pub mod agent;
 pub mod agents;
 pub mod client;
 pub mod error;
+pub mod notifier;
 pub mod server;
 pub mod store;
 pub mod types;
 #[cfg(test)]
 mod tests;
 
+pub use agent::{Agent, EchoAgent};
 pub use client::A2AClient;
 pub use error::{A2AError, A2AResult};
-pub use server::create_router;
+pub use server::{create_router, AuthConfig};
+pub use types::AgentCard;
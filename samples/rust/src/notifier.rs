@@ -0,0 +1,99 @@
+use crate::store::TaskStore;
+use crate::types::{TaskArtifactUpdateEvent, TaskStatusUpdateEvent, TaskUpdate};
+use futures_util::StreamExt;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::wrappers::BroadcastStream;
+use tracing::warn;
+
+/// How many times to attempt delivery before giving up on a single update.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// POST `update` to `task_id`'s registered push-notification webhook, if
+/// one is set, retrying with exponential backoff on non-2xx responses or
+/// transport errors. Delivery failures are logged and swallowed: a client
+/// that can't be reached shouldn't fail the task itself, since `tasks/get`
+/// remains available as a fallback.
+async fn deliver(task_store: &Arc<dyn TaskStore>, client: &Client, task_id: &str, update: &TaskUpdate) {
+    let config = match task_store.get_push_notification_config(task_id) {
+        Ok(Some(config)) => config,
+        Ok(None) => return,
+        Err(err) => {
+            warn!("could not look up push notification config for {}: {}", task_id, err);
+            return;
+        }
+    };
+
+    let mut delay = Duration::from_millis(200);
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = client.post(&config.url).json(update);
+        if let Some(token) = &config.token {
+            request = request.bearer_auth(token);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => warn!(
+                "push notification for task {} to {} returned {}",
+                task_id,
+                config.url,
+                response.status()
+            ),
+            Err(err) => warn!(
+                "push notification for task {} to {} failed: {}",
+                task_id, config.url, err
+            ),
+        }
+
+        if attempt < MAX_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+}
+
+/// Spawn a background task that watches `task_store`'s broadcast channel
+/// and delivers a push notification for every status or artifact change,
+/// the same `TaskUpdate` shapes `tasks/sendSubscribe` streams over SSE.
+/// Delivery for a given task stops once it reaches a terminal state.
+pub fn spawn_delivery_task(task_store: Arc<dyn TaskStore>, http_client: Client) {
+    tokio::spawn(async move {
+        let mut seen_artifacts: HashMap<String, usize> = HashMap::new();
+        let mut updates = BroadcastStream::new(task_store.subscribe());
+
+        while let Some(update) = updates.next().await {
+            // A lagged receiver means we missed some updates, not that the
+            // channel closed; skip it and keep listening for the next one.
+            let Ok(task) = update else { continue };
+
+            let already_seen = seen_artifacts.get(&task.id).copied().unwrap_or(0);
+            if let Some(artifacts) = &task.artifacts {
+                for artifact in &artifacts[already_seen..] {
+                    let update = TaskUpdate::Artifact(TaskArtifactUpdateEvent {
+                        id: task.id.clone(),
+                        artifact: artifact.clone(),
+                        metadata: None,
+                    });
+                    deliver(&task_store, &http_client, &task.id, &update).await;
+                }
+                seen_artifacts.insert(task.id.clone(), artifacts.len());
+            }
+
+            let final_status = task.status.state.is_terminal();
+            let update = TaskUpdate::Status(TaskStatusUpdateEvent {
+                id: task.id.clone(),
+                status: task.status.clone(),
+                final_status,
+                metadata: None,
+            });
+            deliver(&task_store, &http_client, &task.id, &update).await;
+
+            if final_status {
+                seen_artifacts.remove(&task.id);
+            }
+        }
+    });
+}
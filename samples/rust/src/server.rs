@@ -1,92 +1,235 @@
+use crate::agent::Agent;
 use crate::error::{A2AError, A2AResult};
-use crate::store::TaskStore;
+use crate::notifier;
+use crate::store::{MemoryTaskStore, TaskStore};
 use crate::types::{
-    Artifact, CancelTaskRequest, GetTaskRequest, JsonRpcError, JsonRpcResponse, Message, Part,
-    SendTaskRequest, Task, TaskState, TaskStatus, TextPart,
+    A2ARequest, AgentCapabilities, AgentCard, CancelTaskRequest, GetTaskPushNotificationRequest,
+    GetTaskRequest, JsonRpcError, JsonRpcResponse, Message, RequestId, SendTaskRequest,
+    SetTaskPushNotificationRequest, Task, TaskArtifactUpdateEvent, TaskPushNotificationConfig,
+    TaskState, TaskStatus, TaskStatusUpdateEvent, TaskUpdate, PROTOCOL_VERSION,
 };
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Response},
-    routing::post,
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
     Json, Router,
 };
 use chrono::Utc;
-use std::sync::Arc;
+use futures_util::{future, stream, stream::Stream, StreamExt};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, Mutex as AsyncMutex};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 
+/// Default number of workers pulling jobs off the queue when a binary
+/// doesn't pick its own.
+pub const DEFAULT_WORKER_COUNT: usize = 4;
 
-/// Application state
+/// A unit of work enqueued by `tasks/send` for a worker to pick up.
+struct Job {
+    task_id: String,
+    message: Message,
+}
+
+/// Shared-secret auth for the server, checked against every request's
+/// `Authorization: Bearer <token>` header.
 #[derive(Clone)]
+pub struct AuthConfig {
+    pub token: String,
+}
+
+/// Application state
 pub struct AppState {
-    pub task_store: TaskStore,
+    pub task_store: Arc<dyn TaskStore>,
+    job_tx: mpsc::Sender<Job>,
+    active_tasks: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    auth: Option<AuthConfig>,
+    agent_card: AgentCard,
+}
+
+/// An `AgentCard` describing this reference server, used when the caller
+/// doesn't supply its own.
+fn default_agent_card() -> AgentCard {
+    AgentCard {
+        name: "A2A Rust Reference Server".to_string(),
+        description: "Reference implementation of the A2A protocol in Rust".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol_version: PROTOCOL_VERSION.to_string(),
+        url: String::new(),
+        capabilities: AgentCapabilities {
+            streaming: true,
+            push_notifications: true,
+        },
+        skills: Vec::new(),
+        default_input_modes: vec!["text".to_string()],
+        default_output_modes: vec!["text".to_string()],
+    }
+}
+
+/// Serve this agent's `AgentCard` so clients can discover its capabilities
+/// before sending work.
+async fn get_agent_card(State(state): State<Arc<AppState>>) -> Json<AgentCard> {
+    Json(state.agent_card.clone())
+}
+
+/// Reject requests whose `Authorization: Bearer <token>` header doesn't
+/// match `state.auth`. A `None` config leaves the server open, matching the
+/// default (no-auth) behavior.
+async fn require_auth(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(auth) = &state.auth else {
+        return next.run(request).await;
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match provided {
+        Some(token) if token == auth.token => next.run(request).await,
+        _ => A2AError::Unauthorized.into_response(),
+    }
+}
+
+/// Map an error to the `(status, code, message)` triple its JSON-RPC and
+/// HTTP representations are both built from.
+fn error_parts(err: &A2AError) -> (StatusCode, i32, String) {
+    match err {
+        A2AError::TaskNotFound(id) => (
+            StatusCode::NOT_FOUND,
+            -32001,
+            format!("Task not found: {}", id),
+        ),
+        A2AError::InvalidRequest(msg) => (
+            StatusCode::BAD_REQUEST,
+            -32600,
+            format!("Invalid request: {}", msg),
+        ),
+        A2AError::MethodNotFound(method) => (
+            StatusCode::NOT_FOUND,
+            -32601,
+            format!("Method not found: {}", method),
+        ),
+        A2AError::TaskNotCancelable(msg) => (
+            StatusCode::BAD_REQUEST,
+            -32002,
+            format!("Task cannot be canceled: {}", msg),
+        ),
+        A2AError::UnsupportedOperation(msg) => (
+            StatusCode::BAD_REQUEST,
+            -32004,
+            format!("Unsupported operation: {}", msg),
+        ),
+        A2AError::Unauthorized => (
+            StatusCode::UNAUTHORIZED,
+            -32000,
+            "Unauthorized".to_string(),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            -32603,
+            format!("Internal server error: {}", err),
+        ),
+    }
+}
+
+/// Build a JSON-RPC error response `Value` carrying `id`, so a batch item's
+/// error can still echo the caller's original id back.
+fn error_value_with_id(err: &A2AError, id: Option<RequestId>) -> serde_json::Value {
+    let (_, code, message) = error_parts(err);
+    let response = JsonRpcResponse::<()> {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result: None,
+        error: Some(JsonRpcError {
+            code,
+            message,
+            data: None,
+        }),
+    };
+    serde_json::to_value(response).unwrap_or(serde_json::Value::Null)
+}
+
+/// Build a JSON-RPC error response `Value` carrying no id, for call sites
+/// (the top-level, non-batched path below) that don't have one on hand.
+fn error_value(err: &A2AError) -> serde_json::Value {
+    error_value_with_id(err, None)
 }
 
 /// Error response
 impl IntoResponse for A2AError {
     fn into_response(self) -> Response {
-        let (status, error_code, error_message) = match &self {
-            A2AError::TaskNotFound(id) => (
-                StatusCode::NOT_FOUND,
-                -32001,
-                format!("Task not found: {}", id),
-            ),
-            A2AError::InvalidRequest(msg) => (
-                StatusCode::BAD_REQUEST,
-                -32600,
-                format!("Invalid request: {}", msg),
-            ),
-            A2AError::MethodNotFound(method) => (
-                StatusCode::NOT_FOUND,
-                -32601,
-                format!("Method not found: {}", method),
-            ),
-            A2AError::TaskNotCancelable(msg) => (
-                StatusCode::BAD_REQUEST,
-                -32002,
-                format!("Task cannot be canceled: {}", msg),
-            ),
-            A2AError::UnsupportedOperation(msg) => (
-                StatusCode::BAD_REQUEST,
-                -32004,
-                format!("Unsupported operation: {}", msg),
-            ),
-            _ => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                -32603,
-                format!("Internal server error: {}", self),
-            ),
-        };
+        let (status, _, _) = error_parts(&self);
+        (status, Json(error_value(&self))).into_response()
+    }
+}
 
-        let error = JsonRpcError {
-            code: error_code,
-            message: error_message,
-            data: None,
-        };
+/// The id carried by a decoded request, for batch responses where we need
+/// it before we know whether dispatching succeeds.
+fn request_id(request: &A2ARequest) -> Option<RequestId> {
+    match request {
+        A2ARequest::TasksSend(r) => r.id.clone(),
+        A2ARequest::TasksSendSubscribe(r) => r.id.clone(),
+        A2ARequest::TasksGet(r) => r.id.clone(),
+        A2ARequest::TasksCancel(r) => r.id.clone(),
+        A2ARequest::PushNotificationSet(r) => r.id.clone(),
+        A2ARequest::PushNotificationGet(r) => r.id.clone(),
+        A2ARequest::GetCapabilities(r) => r.id.clone(),
+        A2ARequest::Unknown { id, .. } => id.clone(),
+    }
+}
 
-        let response = JsonRpcResponse::<()> {
-            jsonrpc: "2.0".to_string(),
-            id: None,
-            result: None,
-            error: Some(error),
-        };
+/// Reject methods this agent's Agent Card doesn't advertise before they
+/// reach a handler, e.g. `tasks/sendSubscribe` when `streaming` is off.
+fn reject_unadvertised(state: &AppState, request: &A2ARequest) -> A2AResult<()> {
+    let capabilities = &state.agent_card.capabilities;
 
-        (status, Json(response)).into_response()
+    match request {
+        A2ARequest::TasksSendSubscribe(_) if !capabilities.streaming => {
+            Err(A2AError::UnsupportedOperation(
+                "tasks/sendSubscribe: this agent does not support streaming".to_string(),
+            ))
+        }
+        A2ARequest::PushNotificationSet(_) | A2ARequest::PushNotificationGet(_)
+            if !capabilities.push_notifications =>
+        {
+            Err(A2AError::UnsupportedOperation(
+                "tasks/pushNotification: this agent does not support push notifications"
+                    .to_string(),
+            ))
+        }
+        _ => Ok(()),
     }
 }
 
-/// Handle JSON-RPC requests
-async fn handle_request(
-    State(state): State<Arc<AppState>>,
-    Json(payload): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, A2AError> {
-    let method = payload["method"]
-        .as_str()
-        .ok_or_else(|| A2AError::InvalidRequest("Missing method".to_string()))?;
-
-    match method {
-        "tasks/send" => {
-            let request: SendTaskRequest = serde_json::from_value(payload.clone())?;
+/// Where a dispatched request's response ends up: most methods produce a
+/// JSON-RPC response value, but `tasks/sendSubscribe` hands back a live SSE
+/// stream instead.
+enum Dispatched {
+    Value(serde_json::Value),
+    Sse(Response),
+}
+
+/// Run one decoded request against `state`, enforcing capability gating
+/// first. Shared by the single-request and batch-request paths below.
+async fn dispatch(state: Arc<AppState>, request: A2ARequest) -> A2AResult<Dispatched> {
+    reject_unadvertised(&state, &request)?;
+
+    match request {
+        A2ARequest::TasksSend(request) => {
             let request_id = request.id.clone();
             let task = handle_send_task(state, request).await?;
             let response = JsonRpcResponse {
@@ -95,10 +238,12 @@ async fn handle_request(
                 result: Some(task),
                 error: None,
             };
-            Ok(Json(serde_json::to_value(response)?))
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
         }
-        "tasks/get" => {
-            let request: GetTaskRequest = serde_json::from_value(payload.clone())?;
+        A2ARequest::TasksSendSubscribe(request) => Ok(Dispatched::Sse(
+            handle_send_task_subscribe(state, request).await?.into_response(),
+        )),
+        A2ARequest::TasksGet(request) => {
             let request_id = request.id.clone();
             let task = handle_get_task(state, request).await?;
             let response = JsonRpcResponse {
@@ -107,10 +252,9 @@ async fn handle_request(
                 result: Some(task),
                 error: None,
             };
-            Ok(Json(serde_json::to_value(response)?))
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
         }
-        "tasks/cancel" => {
-            let request: CancelTaskRequest = serde_json::from_value(payload.clone())?;
+        A2ARequest::TasksCancel(request) => {
             let request_id = request.id.clone();
             let task = handle_cancel_task(state, request).await?;
             let response = JsonRpcResponse {
@@ -119,129 +263,461 @@ async fn handle_request(
                 result: Some(task),
                 error: None,
             };
-            Ok(Json(serde_json::to_value(response)?))
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
         }
-        _ => Err(A2AError::MethodNotFound(method.to_string())),
+        A2ARequest::PushNotificationSet(request) => {
+            let request_id = request.id.clone();
+            let config = handle_set_push_notification(state, request).await?;
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request_id,
+                result: Some(config),
+                error: None,
+            };
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
+        }
+        A2ARequest::PushNotificationGet(request) => {
+            let request_id = request.id.clone();
+            let config = handle_get_push_notification(state, request).await?;
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request_id,
+                result: Some(config),
+                error: None,
+            };
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
+        }
+        A2ARequest::GetCapabilities(request) => {
+            let request_id = request.id.clone();
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request_id,
+                result: Some(state.agent_card.capabilities.clone()),
+                error: None,
+            };
+            Ok(Dispatched::Value(serde_json::to_value(response)?))
+        }
+        A2ARequest::Unknown { method, .. } => Err(A2AError::MethodNotFound(method)),
     }
 }
 
-/// Handle send task request
-async fn handle_send_task(
+/// Run one request drawn from a batch array, returning its response
+/// `Value` — or `None` if it was a notification (no `id`), which per the
+/// JSON-RPC spec gets no response even if dispatching it fails.
+/// `tasks/sendSubscribe` can't be folded into a batch's JSON array, so it's
+/// rejected there instead of silently downgraded to a one-shot response.
+async fn dispatch_batch_item(
     state: Arc<AppState>,
-    request: SendTaskRequest,
-) -> A2AResult<Task> {
-    let task_id = request.params.id.clone();
-    let session_id = request.params.session_id.clone();
-    let message = request.params.message.clone();
-
-    // Check if task exists
-    let existing_task = state.task_store.get_task(&task_id);
-
-    match existing_task {
-        Ok(task) => {
-            // Task exists, update it
-            if task.status.state == TaskState::InputRequired {
-                // Process the new message
-                let response_message = process_message(&message)?;
-
-                // Update task status
-                let new_status = TaskStatus {
-                    state: TaskState::Completed,
-                    message: Some(response_message),
-                    timestamp: Utc::now(),
-                };
+    payload: serde_json::Value,
+) -> Option<serde_json::Value> {
+    let request: A2ARequest = match serde_json::from_value(payload) {
+        Ok(request) => request,
+        Err(err) => return Some(error_value(&A2AError::from(err))),
+    };
+    let id = request_id(&request);
+    let is_notification = id.is_none();
+
+    match dispatch(state, request).await {
+        Ok(Dispatched::Value(_)) if is_notification => None,
+        Ok(Dispatched::Value(value)) => Some(value),
+        Ok(Dispatched::Sse(_)) => Some(error_value_with_id(
+            &A2AError::UnsupportedOperation(
+                "tasks/sendSubscribe cannot be used inside a batch request".to_string(),
+            ),
+            id,
+        )),
+        Err(_) if is_notification => None,
+        Err(err) => Some(error_value_with_id(&err, id)),
+    }
+}
 
-                state.task_store.update_task_status(&task_id, new_status)
-            } else {
-                Err(A2AError::InvalidRequest(format!(
-                    "Task {} is not in input-required state",
-                    task_id
-                )))
+/// Handle JSON-RPC requests. A JSON array payload is treated as a batch:
+/// each element is dispatched independently and the results collected into
+/// a matching response array, per the JSON-RPC 2.0 batch spec.
+async fn handle_request(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<serde_json::Value>,
+) -> Result<Response, A2AError> {
+    match payload {
+        serde_json::Value::Array(items) => {
+            let mut responses = Vec::with_capacity(items.len());
+            for item in items {
+                if let Some(value) = dispatch_batch_item(state.clone(), item).await {
+                    responses.push(value);
+                }
             }
+            Ok(Json(serde_json::Value::Array(responses)).into_response())
         }
-        Err(_) => {
-            // Task doesn't exist, create a new one
-            let response_message = process_message(&message)?;
+        single => {
+            let request: A2ARequest = serde_json::from_value(single)?;
+            match dispatch(state, request).await? {
+                Dispatched::Value(value) => Ok(Json(value).into_response()),
+                Dispatched::Sse(response) => Ok(response),
+            }
+        }
+    }
+}
 
-            // Create a new task
+/// Create a fresh task in `Submitted` state, or resume one already waiting
+/// on `InputRequired`. Shared by `tasks/send` and `tasks/sendSubscribe`,
+/// which only differ in how they report progress back to the caller.
+fn create_or_resume_task(
+    state: &AppState,
+    task_id: String,
+    session_id: Option<String>,
+) -> A2AResult<Task> {
+    match state.task_store.get_task(&task_id) {
+        Ok(existing) if existing.status.state == TaskState::InputRequired => {
+            state.task_store.update_task_status(
+                &task_id,
+                TaskStatus {
+                    state: TaskState::Submitted,
+                    message: None,
+                    timestamp: Utc::now(),
+                },
+            )
+        }
+        Ok(existing) => Err(A2AError::InvalidRequest(format!(
+            "Task {} is not in input-required state",
+            existing.id
+        ))),
+        Err(_) => {
             let task = Task {
                 id: task_id,
                 session_id,
                 status: TaskStatus {
-                    state: TaskState::Completed,
-                    message: Some(response_message.clone()),
+                    state: TaskState::Submitted,
+                    message: None,
                     timestamp: Utc::now(),
                 },
-                artifacts: Some(vec![Artifact {
-                    name: Some("result".to_string()),
-                    description: Some("Task result".to_string()),
-                    parts: vec![Part::Text(TextPart {
-                        part_type: "text".to_string(),
-                        text: "This is a sample artifact from the Rust A2A server.".to_string(),
-                        metadata: None,
-                    })],
-                    index: 0,
-                    append: None,
-                    metadata: None,
-                    last_chunk: Some(true),
-                }]),
+                artifacts: None,
+                history: None,
                 metadata: None,
             };
-
             state.task_store.create_task(task)
         }
     }
 }
 
-/// Handle get task request
+/// Handle send task request: create (or resume) the task in `Submitted`
+/// state and hand it off to the worker queue, returning immediately so the
+/// caller polls via `tasks/get` instead of blocking on processing.
+async fn handle_send_task(
+    state: Arc<AppState>,
+    request: SendTaskRequest,
+) -> A2AResult<Task> {
+    let task = create_or_resume_task(
+        &state,
+        request.params.id.clone(),
+        request.params.session_id.clone(),
+    )?;
+
+    state
+        .job_tx
+        .send(Job {
+            task_id: task.id.clone(),
+            message: request.params.message.clone(),
+        })
+        .await
+        .map_err(|_| A2AError::InternalServerError("worker queue is closed".to_string()))?;
+
+    Ok(task)
+}
+
+/// Handle get task request. `history_length` trims the returned
+/// `history` to the most recent N entries; omitted or negative values
+/// return the full log.
 async fn handle_get_task(
     state: Arc<AppState>,
     request: GetTaskRequest,
 ) -> A2AResult<Task> {
     let task_id = request.params.id.clone();
-    state.task_store.get_task(&task_id)
+    let mut task = state.task_store.get_task(&task_id)?;
+
+    if let Some(history_length) = request.params.history_length {
+        if history_length >= 0 {
+            let history_length = history_length as usize;
+            if let Some(history) = &mut task.history {
+                let skip = history.len().saturating_sub(history_length);
+                history.drain(..skip);
+            }
+        }
+    }
+
+    Ok(task)
 }
 
-/// Handle cancel task request
+/// Handle cancel task request. Flips the stored status first (which
+/// rejects cancellation of already-terminal tasks), then signals the
+/// in-flight worker, if any, to stop processing it.
 async fn handle_cancel_task(
     state: Arc<AppState>,
     request: CancelTaskRequest,
 ) -> A2AResult<Task> {
     let task_id = request.params.id.clone();
-    state.task_store.cancel_task(&task_id)
-}
-
-/// Process a message and generate a response
-fn process_message(message: &Message) -> A2AResult<Message> {
-    // Extract text from the message
-    let text = message
-        .parts
-        .iter()
-        .filter_map(|part| match part {
-            Part::Text(text_part) => Some(text_part.text.clone()),
-            _ => None,
-        })
-        .collect::<Vec<String>>()
-        .join(" ");
-
-    // Generate a simple response
-    let response_text = format!("Rust A2A server received: {}", text);
-
-    Ok(Message {
-        role: "agent".to_string(),
-        parts: vec![Part::Text(TextPart {
-            part_type: "text".to_string(),
-            text: response_text,
-            metadata: None,
-        })],
-        metadata: None,
+    let task = state.task_store.cancel_task(&task_id)?;
+
+    if let Some(token) = state.active_tasks.lock().unwrap().remove(&task_id) {
+        token.cancel();
+    }
+
+    // The background delivery task (spawned in `create_router_with_store`)
+    // picks this cancellation up off the broadcast channel on its own.
+
+    Ok(task)
+}
+
+/// Register the push-notification webhook for a task.
+async fn handle_set_push_notification(
+    state: Arc<AppState>,
+    request: SetTaskPushNotificationRequest,
+) -> A2AResult<TaskPushNotificationConfig> {
+    let TaskPushNotificationConfig {
+        id,
+        push_notification_config,
+    } = request.params;
+
+    // Make sure the task actually exists before accepting a webhook for it.
+    state.task_store.get_task(&id)?;
+    state
+        .task_store
+        .set_push_notification_config(&id, push_notification_config.clone())?;
+
+    Ok(TaskPushNotificationConfig {
+        id,
+        push_notification_config,
+    })
+}
+
+/// Read back the push-notification webhook registered for a task.
+async fn handle_get_push_notification(
+    state: Arc<AppState>,
+    request: GetTaskPushNotificationRequest,
+) -> A2AResult<TaskPushNotificationConfig> {
+    let task_id = request.params.id.clone();
+    let push_notification_config = state
+        .task_store
+        .get_push_notification_config(&task_id)?
+        .ok_or_else(|| A2AError::TaskNotFound(task_id.clone()))?;
+
+    Ok(TaskPushNotificationConfig {
+        id: task_id,
+        push_notification_config,
     })
 }
 
-/// Create the Axum router
-pub fn create_router() -> Router {
-    let task_store = TaskStore::new();
-    let app_state = Arc::new(AppState { task_store });
+/// Handle a `tasks/sendSubscribe` request: create (or resume) the task,
+/// enqueue it on the same job queue `tasks/send` uses, and stream its
+/// progress back as SSE frames by following `state.task_store`'s broadcast
+/// channel, rather than driving the work inline. The stream closes once a
+/// `TaskUpdate::is_final` event has been forwarded.
+async fn handle_send_task_subscribe(
+    state: Arc<AppState>,
+    request: SendTaskRequest,
+) -> A2AResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let request_id = request.id.clone();
+    let task = create_or_resume_task(
+        &state,
+        request.params.id.clone(),
+        request.params.session_id.clone(),
+    )?;
+    let task_id = task.id.clone();
+    let mut seen_artifacts = task.artifacts.as_ref().map_or(0, Vec::len);
+
+    // Subscribe before enqueueing so a worker that finishes quickly can't
+    // broadcast its updates before we're listening for them.
+    let updates = BroadcastStream::new(state.task_store.subscribe());
+
+    state
+        .job_tx
+        .send(Job {
+            task_id: task_id.clone(),
+            message: request.params.message.clone(),
+        })
+        .await
+        .map_err(|_| A2AError::InternalServerError("worker queue is closed".to_string()))?;
+
+    let stream = updates
+        .filter_map(|update| future::ready(update.ok()))
+        .filter(move |task| future::ready(task.id == task_id))
+        .flat_map(move |task| {
+            let mut events = Vec::new();
+
+            if let Some(artifacts) = &task.artifacts {
+                for artifact in &artifacts[seen_artifacts..] {
+                    events.push(TaskUpdate::Artifact(TaskArtifactUpdateEvent {
+                        id: task.id.clone(),
+                        artifact: artifact.clone(),
+                        metadata: None,
+                    }));
+                }
+                seen_artifacts = artifacts.len();
+            }
+
+            events.push(TaskUpdate::Status(TaskStatusUpdateEvent {
+                id: task.id.clone(),
+                status: task.status.clone(),
+                final_status: task.status.state.is_terminal(),
+                metadata: None,
+            }));
+
+            stream::iter(events)
+        })
+        .scan(false, |done, update| {
+            if *done {
+                return future::ready(None);
+            }
+            *done = update.is_final();
+            future::ready(Some(update))
+        })
+        .map(move |update| {
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request_id.clone(),
+                result: Some(update),
+                error: None,
+            };
+            Ok(Event::default()
+                .json_data(response)
+                .unwrap_or_else(|_| Event::default()))
+        });
+
+    Ok(Sse::new(stream))
+}
+
+/// Spawn `worker_count` tasks that share `job_rx` and process jobs as they
+/// arrive, each tracked in `active_tasks` for the duration of the work so
+/// `tasks/cancel` can abort it.
+fn spawn_workers(
+    task_store: Arc<dyn TaskStore>,
+    active_tasks: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    agent: Arc<dyn Agent>,
+    job_rx: mpsc::Receiver<Job>,
+    worker_count: usize,
+) {
+    let job_rx = Arc::new(AsyncMutex::new(job_rx));
+
+    for _ in 0..worker_count {
+        let task_store = task_store.clone();
+        let active_tasks = active_tasks.clone();
+        let agent = agent.clone();
+        let job_rx = job_rx.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let job = {
+                    let mut rx = job_rx.lock().await;
+                    rx.recv().await
+                };
+
+                match job {
+                    Some(job) => run_job(&task_store, &active_tasks, &agent, job).await,
+                    None => break,
+                }
+            }
+        });
+    }
+}
+
+/// Run a single job to completion (or cancellation) by handing it to
+/// `agent`, which drives the store's status/artifact updates itself. The
+/// background delivery task (see `notifier::spawn_delivery_task`) picks up
+/// every resulting status/artifact change on its own.
+async fn run_job(
+    task_store: &Arc<dyn TaskStore>,
+    active_tasks: &Arc<Mutex<HashMap<String, CancellationToken>>>,
+    agent: &Arc<dyn Agent>,
+    job: Job,
+) {
+    // The task may already have been canceled while it sat in the queue,
+    // before any worker picked it up; don't resurrect it by running the
+    // agent anyway.
+    match task_store.get_task(&job.task_id) {
+        Ok(task) if task.status.state.is_terminal() => return,
+        Ok(_) => {}
+        Err(_) => return,
+    }
+
+    let token = CancellationToken::new();
+    active_tasks
+        .lock()
+        .unwrap()
+        .insert(job.task_id.clone(), token.clone());
+
+    let outcome = tokio::select! {
+        _ = token.cancelled() => None,
+        result = agent.handle(&job.task_id, job.message, task_store) => Some(result),
+    };
+
+    active_tasks.lock().unwrap().remove(&job.task_id);
+
+    let Some(result) = outcome else {
+        // Canceled mid-flight; tasks/cancel already left the store in
+        // `Canceled`, so there's nothing left to write back.
+        return;
+    };
+
+    if result.is_err() {
+        let failed = TaskStatus {
+            state: TaskState::Failed,
+            message: None,
+            timestamp: Utc::now(),
+        };
+        let _ = task_store.update_task_status(&job.task_id, failed);
+    }
+}
+
+/// Create the Axum router with the default in-memory store, backed by
+/// `worker_count` background workers pulling off the `tasks/send` job queue
+/// and dispatching each job to `agent` (use `EchoAgent` for the reference
+/// behavior, or plug in your own). `auth` is `None` for an open server, or
+/// `Some` to require a matching bearer token on every request. `agent_card`
+/// is served from `/.well-known/agent.json`; `None` falls back to
+/// `default_agent_card()`.
+pub fn create_router(
+    worker_count: usize,
+    auth: Option<AuthConfig>,
+    agent_card: Option<AgentCard>,
+    agent: impl Agent + 'static,
+) -> Router {
+    create_router_with_store(
+        Arc::new(MemoryTaskStore::new()),
+        worker_count,
+        auth,
+        agent_card,
+        agent,
+    )
+}
+
+/// Create the Axum router against any `TaskStore` implementation (e.g.
+/// `SqliteTaskStore` for a persistent deployment).
+pub fn create_router_with_store(
+    task_store: Arc<dyn TaskStore>,
+    worker_count: usize,
+    auth: Option<AuthConfig>,
+    agent_card: Option<AgentCard>,
+    agent: impl Agent + 'static,
+) -> Router {
+    let (job_tx, job_rx) = mpsc::channel::<Job>(100);
+    let active_tasks = Arc::new(Mutex::new(HashMap::new()));
+    let agent: Arc<dyn Agent> = Arc::new(agent);
+
+    spawn_workers(
+        task_store.clone(),
+        active_tasks.clone(),
+        agent,
+        job_rx,
+        worker_count,
+    );
+    notifier::spawn_delivery_task(task_store.clone(), reqwest::Client::new());
+
+    let app_state = Arc::new(AppState {
+        task_store,
+        job_tx,
+        active_tasks,
+        auth,
+        agent_card: agent_card.unwrap_or_else(default_agent_card),
+    });
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -250,6 +726,155 @@ pub fn create_router() -> Router {
 
     Router::new()
         .route("/", post(handle_request))
+        .route("/.well-known/agent.json", get(get_agent_card))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ))
         .layer(cors)
         .with_state(app_state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::EchoAgent;
+    use crate::types::TaskQueryParams;
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    fn test_state() -> Arc<AppState> {
+        let (job_tx, _job_rx) = mpsc::channel(100);
+        Arc::new(AppState {
+            task_store: Arc::new(MemoryTaskStore::new()),
+            job_tx,
+            active_tasks: Arc::new(Mutex::new(HashMap::new())),
+            auth: None,
+            agent_card: default_agent_card(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_batch_skips_notifications_but_keeps_unknown_method_ids() {
+        let state = test_state();
+
+        let known = json!({"jsonrpc": "2.0", "id": "a", "method": "agent/getCapabilities"});
+        let known_notification = json!({"jsonrpc": "2.0", "method": "agent/getCapabilities"});
+        let unknown_with_id = json!({"jsonrpc": "2.0", "id": "b", "method": "no/such/method"});
+        let unknown_notification = json!({"jsonrpc": "2.0", "method": "no/such/method"});
+
+        let known_response = dispatch_batch_item(state.clone(), known).await;
+        assert!(known_response.is_some());
+
+        let known_notification_response =
+            dispatch_batch_item(state.clone(), known_notification).await;
+        assert!(
+            known_notification_response.is_none(),
+            "a notification that dispatches successfully must still get no response"
+        );
+
+        let unknown_response = dispatch_batch_item(state.clone(), unknown_with_id)
+            .await
+            .expect("an unrecognized method with an id must still get a response");
+        assert_eq!(unknown_response["id"], json!("b"));
+        assert_eq!(unknown_response["error"]["code"], json!(-32601));
+
+        let notification_response = dispatch_batch_item(state, unknown_notification).await;
+        assert!(
+            notification_response.is_none(),
+            "a true notification (no id) must not get a response even on error"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auth_gates_both_json_rpc_and_agent_card_routes() {
+        let auth = AuthConfig {
+            token: "secret".to_string(),
+        };
+        let app = create_router(1, Some(auth), None, EchoAgent);
+
+        let unauthorized = app
+            .clone()
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/.well-known/agent.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+        let authorized = app
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/.well-known/agent.json")
+                    .header("Authorization", "Bearer secret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(authorized.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_get_task_history_length_trims_but_negative_leaves_it_untouched() {
+        let state = test_state();
+        let task_id = Uuid::new_v4().to_string();
+
+        let history: Vec<Task> = (0..5)
+            .map(|i| Task {
+                id: task_id.clone(),
+                session_id: None,
+                status: TaskStatus {
+                    state: TaskState::Working,
+                    message: None,
+                    timestamp: Utc::now(),
+                },
+                artifacts: None,
+                history: None,
+                metadata: Some(HashMap::from([("seq".to_string(), json!(i))])),
+            })
+            .collect();
+
+        state
+            .task_store
+            .create_task(Task {
+                id: task_id.clone(),
+                session_id: None,
+                status: TaskStatus {
+                    state: TaskState::Working,
+                    message: None,
+                    timestamp: Utc::now(),
+                },
+                artifacts: None,
+                history: Some(history),
+                metadata: None,
+            })
+            .unwrap();
+
+        let get = |history_length| GetTaskRequest {
+            params: TaskQueryParams {
+                id: task_id.clone(),
+                history_length,
+                metadata: None,
+            },
+            ..GetTaskRequest::default()
+        };
+
+        let trimmed = handle_get_task(state.clone(), get(Some(2))).await.unwrap();
+        assert_eq!(trimmed.history.unwrap().len(), 2);
+
+        let untouched = handle_get_task(state.clone(), get(Some(-1)))
+            .await
+            .unwrap();
+        assert_eq!(untouched.history.unwrap().len(), 5);
+
+        let omitted = handle_get_task(state, get(None)).await.unwrap();
+        assert_eq!(omitted.history.unwrap().len(), 5);
+    }
+}
@@ -1,31 +1,43 @@
+use super::TaskStore;
 use crate::error::{A2AError, A2AResult};
-use crate::types::{Artifact, Task, TaskState, TaskStatus};
+use crate::types::{Artifact, PushNotificationConfig, Task, TaskState, TaskStatus};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 
-/// A simple in-memory store for tasks
+/// A simple in-memory store for tasks. Tasks are lost on restart; use
+/// `SqliteTaskStore` when that matters.
 #[derive(Clone)]
-pub struct TaskStore {
+pub struct MemoryTaskStore {
     tasks: Arc<Mutex<HashMap<String, Task>>>,
+    push_configs: Arc<Mutex<HashMap<String, PushNotificationConfig>>>,
     task_updates: broadcast::Sender<Task>,
 }
 
-impl TaskStore {
+impl MemoryTaskStore {
     pub fn new() -> Self {
         let (tx, _) = broadcast::channel(100);
         Self {
             tasks: Arc::new(Mutex::new(HashMap::new())),
+            push_configs: Arc::new(Mutex::new(HashMap::new())),
             task_updates: tx,
         }
     }
+}
+
+impl Default for MemoryTaskStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    pub fn subscribe(&self) -> broadcast::Receiver<Task> {
+impl TaskStore for MemoryTaskStore {
+    fn subscribe(&self) -> broadcast::Receiver<Task> {
         self.task_updates.subscribe()
     }
 
-    pub fn get_task(&self, id: &str) -> A2AResult<Task> {
+    fn get_task(&self, id: &str) -> A2AResult<Task> {
         let tasks = self.tasks.lock().unwrap();
         tasks
             .get(id)
@@ -33,50 +45,59 @@ impl TaskStore {
             .ok_or_else(|| A2AError::TaskNotFound(id.to_string()))
     }
 
-    pub fn create_task(&self, task: Task) -> A2AResult<Task> {
+    fn create_task(&self, task: Task) -> A2AResult<Task> {
         let mut tasks = self.tasks.lock().unwrap();
         tasks.insert(task.id.clone(), task.clone());
         let _ = self.task_updates.send(task.clone());
         Ok(task)
     }
 
-    pub fn update_task_status(&self, id: &str, status: TaskStatus) -> A2AResult<Task> {
+    fn update_task_status(&self, id: &str, status: TaskStatus) -> A2AResult<Task> {
         let mut tasks = self.tasks.lock().unwrap();
         let task = tasks
             .get_mut(id)
             .ok_or_else(|| A2AError::TaskNotFound(id.to_string()))?;
-        
+
+        if task.status.state.is_terminal() {
+            // Already finished (e.g. canceled while still queued); don't
+            // let a stale in-flight agent or runner resurrect it.
+            return Ok(task.clone());
+        }
+
+        if let Some(message) = &status.message {
+            task.history.get_or_insert_with(Vec::new).push(message.clone());
+        }
         task.status = status;
         let updated_task = task.clone();
         let _ = self.task_updates.send(updated_task.clone());
         Ok(updated_task)
     }
 
-    pub fn add_artifact(&self, id: &str, artifact: Artifact) -> A2AResult<Task> {
+    fn add_artifact(&self, id: &str, artifact: Artifact) -> A2AResult<Task> {
         let mut tasks = self.tasks.lock().unwrap();
         let task = tasks
             .get_mut(id)
             .ok_or_else(|| A2AError::TaskNotFound(id.to_string()))?;
-        
+
         if task.artifacts.is_none() {
             task.artifacts = Some(vec![]);
         }
-        
+
         if let Some(artifacts) = &mut task.artifacts {
             artifacts.push(artifact);
         }
-        
+
         let updated_task = task.clone();
         let _ = self.task_updates.send(updated_task.clone());
         Ok(updated_task)
     }
 
-    pub fn cancel_task(&self, id: &str) -> A2AResult<Task> {
+    fn cancel_task(&self, id: &str) -> A2AResult<Task> {
         let mut tasks = self.tasks.lock().unwrap();
         let task = tasks
             .get_mut(id)
             .ok_or_else(|| A2AError::TaskNotFound(id.to_string()))?;
-        
+
         // Only tasks in certain states can be canceled
         match task.status.state {
             TaskState::Submitted | TaskState::Working | TaskState::InputRequired => {
@@ -95,4 +116,17 @@ impl TaskStore {
             ))),
         }
     }
+
+    fn set_push_notification_config(
+        &self,
+        id: &str,
+        config: PushNotificationConfig,
+    ) -> A2AResult<()> {
+        self.push_configs.lock().unwrap().insert(id.to_string(), config);
+        Ok(())
+    }
+
+    fn get_push_notification_config(&self, id: &str) -> A2AResult<Option<PushNotificationConfig>> {
+        Ok(self.push_configs.lock().unwrap().get(id).cloned())
+    }
 }
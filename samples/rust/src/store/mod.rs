@@ -0,0 +1,38 @@
+use crate::error::A2AResult;
+use crate::types::{Artifact, PushNotificationConfig, Task, TaskStatus};
+use tokio::sync::broadcast;
+
+mod memory;
+mod sqlite;
+
+pub use memory::MemoryTaskStore;
+pub use sqlite::SqliteTaskStore;
+
+/// Storage backend for tasks. `MemoryTaskStore` is the default, in-process
+/// implementation; `SqliteTaskStore` persists tasks so they survive a
+/// restart. `AppState` holds this behind an `Arc<dyn TaskStore>` so a server
+/// binary can pick whichever backend it needs without the router caring.
+pub trait TaskStore: Send + Sync {
+    /// Subscribe to a feed of tasks as they're created or updated.
+    fn subscribe(&self) -> broadcast::Receiver<Task>;
+
+    fn get_task(&self, id: &str) -> A2AResult<Task>;
+
+    fn create_task(&self, task: Task) -> A2AResult<Task>;
+
+    fn update_task_status(&self, id: &str, status: TaskStatus) -> A2AResult<Task>;
+
+    fn add_artifact(&self, id: &str, artifact: Artifact) -> A2AResult<Task>;
+
+    fn cancel_task(&self, id: &str) -> A2AResult<Task>;
+
+    /// Register (or replace) the push-notification webhook for a task.
+    fn set_push_notification_config(
+        &self,
+        id: &str,
+        config: PushNotificationConfig,
+    ) -> A2AResult<()>;
+
+    /// Read back a task's registered push-notification webhook, if any.
+    fn get_push_notification_config(&self, id: &str) -> A2AResult<Option<PushNotificationConfig>>;
+}
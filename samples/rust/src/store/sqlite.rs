@@ -0,0 +1,110 @@
+mod dbctx;
+mod sql;
+
+use super::TaskStore;
+use crate::error::A2AResult;
+use crate::types::{Artifact, PushNotificationConfig, Task, TaskStatus};
+use dbctx::DbCtx;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// A `TaskStore` backed by a local SQLite database, so tasks survive a
+/// restart. Queries live in `sql`; this type just wires them up to the
+/// `TaskStore` trait and re-broadcasts updates the same way
+/// `MemoryTaskStore` does.
+#[derive(Clone)]
+pub struct SqliteTaskStore {
+    db: Arc<DbCtx>,
+    task_updates: broadcast::Sender<Task>,
+}
+
+impl SqliteTaskStore {
+    /// Open (or create) the database file at `path`.
+    pub fn open(path: &str) -> A2AResult<Self> {
+        let (tx, _) = broadcast::channel(100);
+        Ok(Self {
+            db: Arc::new(DbCtx::open(path)?),
+            task_updates: tx,
+        })
+    }
+
+    /// An in-memory SQLite database, useful for tests that want the same
+    /// code path as the persistent store without leaving a file behind.
+    pub fn in_memory() -> A2AResult<Self> {
+        let (tx, _) = broadcast::channel(100);
+        Ok(Self {
+            db: Arc::new(DbCtx::in_memory()?),
+            task_updates: tx,
+        })
+    }
+}
+
+impl TaskStore for SqliteTaskStore {
+    fn subscribe(&self) -> broadcast::Receiver<Task> {
+        self.task_updates.subscribe()
+    }
+
+    fn get_task(&self, id: &str) -> A2AResult<Task> {
+        with_conn_blocking(&self.db, |conn| sql::get_task(conn, id))
+    }
+
+    fn create_task(&self, task: Task) -> A2AResult<Task> {
+        with_conn_blocking(&self.db, |conn| sql::insert_task(conn, &task))?;
+        let _ = self.task_updates.send(task.clone());
+        Ok(task)
+    }
+
+    fn update_task_status(&self, id: &str, status: TaskStatus) -> A2AResult<Task> {
+        let task = with_conn_blocking(&self.db, |conn| sql::update_status(conn, id, &status))?;
+        let _ = self.task_updates.send(task.clone());
+        Ok(task)
+    }
+
+    fn add_artifact(&self, id: &str, artifact: Artifact) -> A2AResult<Task> {
+        let task = with_conn_blocking(&self.db, |conn| sql::add_artifact(conn, id, &artifact))?;
+        let _ = self.task_updates.send(task.clone());
+        Ok(task)
+    }
+
+    fn cancel_task(&self, id: &str) -> A2AResult<Task> {
+        let task = with_conn_blocking(&self.db, |conn| sql::cancel_task(conn, id))?;
+        let _ = self.task_updates.send(task.clone());
+        Ok(task)
+    }
+
+    fn set_push_notification_config(
+        &self,
+        id: &str,
+        config: PushNotificationConfig,
+    ) -> A2AResult<()> {
+        with_conn_blocking(&self.db, |conn| {
+            sql::set_push_notification_config(conn, id, &config)
+        })
+    }
+
+    fn get_push_notification_config(&self, id: &str) -> A2AResult<Option<PushNotificationConfig>> {
+        with_conn_blocking(&self.db, |conn| {
+            sql::get_push_notification_config(conn, id)
+        })
+    }
+}
+
+/// Run `f` against a checked-out connection via `DbCtx::with_conn`. When
+/// called from an async task (the normal case: server handlers going
+/// through `Arc<dyn TaskStore>`), this runs inside
+/// `tokio::task::block_in_place` so the wait for a free connection (or the
+/// blocking SQLite call itself) doesn't stall that worker thread. Plain
+/// synchronous callers (e.g. this module's own tests, which exercise
+/// `SqliteTaskStore` with no runtime running at all) fall back to calling
+/// `f` directly, since `block_in_place` panics outside a multi-threaded
+/// Tokio runtime. `TaskStore` stays a synchronous trait - this is the one
+/// place that bridges it into the async handlers that call it.
+fn with_conn_blocking<T>(
+    db: &DbCtx,
+    f: impl FnOnce(&rusqlite::Connection) -> A2AResult<T>,
+) -> A2AResult<T> {
+    match tokio::runtime::Handle::try_current() {
+        Ok(_) => tokio::task::block_in_place(|| db.with_conn(f)),
+        Err(_) => db.with_conn(f),
+    }
+}
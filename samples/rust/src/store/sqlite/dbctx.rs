@@ -0,0 +1,68 @@
+use crate::error::A2AResult;
+use rusqlite::Connection;
+use std::sync::{Condvar, Mutex};
+
+/// Connections kept open for a file-backed database. `rusqlite::Connection`
+/// isn't `Sync`, so concurrent callers each need their own checked-out
+/// connection; the single shared connection this used to be serialized
+/// every query even when SQLite itself could have served a few of them in
+/// parallel (e.g. two reads). `:memory:` databases are private to the
+/// connection that created them, so `in_memory` sticks to a pool of one -
+/// pooling it for real would just hand every other checkout an empty,
+/// disconnected database.
+const FILE_POOL_SIZE: usize = 4;
+
+/// A small fixed-size pool of `rusqlite` connections backing a
+/// `SqliteTaskStore`. Queries themselves live in `super::sql`; this type
+/// just checks a connection out per call and blocks (via a condvar, not a
+/// spin loop) if they're all in use.
+pub struct DbCtx {
+    connections: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl DbCtx {
+    pub fn open(path: &str) -> A2AResult<Self> {
+        let mut connections = Vec::with_capacity(FILE_POOL_SIZE);
+        for _ in 0..FILE_POOL_SIZE {
+            let conn = Connection::open(path)?;
+            super::sql::init(&conn)?;
+            connections.push(conn);
+        }
+        Ok(Self {
+            connections: Mutex::new(connections),
+            available: Condvar::new(),
+        })
+    }
+
+    pub fn in_memory() -> A2AResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        super::sql::init(&conn)?;
+        Ok(Self {
+            connections: Mutex::new(vec![conn]),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Check a connection out of the pool, run `f` against it, and return it
+    /// once `f` is done. Blocks the calling thread if every connection is
+    /// currently checked out, so callers should run this inside
+    /// `tokio::task::block_in_place` rather than directly on an async task.
+    pub fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> A2AResult<T>) -> A2AResult<T> {
+        let mut pool = self.connections.lock().unwrap();
+        let conn = loop {
+            if let Some(conn) = pool.pop() {
+                break conn;
+            }
+            pool = self.available.wait(pool).unwrap();
+        };
+        drop(pool);
+
+        let result = f(&conn);
+
+        self.connections.lock().unwrap().push(conn);
+        self.available.notify_one();
+
+        result
+    }
+}
@@ -0,0 +1,200 @@
+use crate::error::{A2AError, A2AResult};
+use crate::types::{Artifact, PushNotificationConfig, Task, TaskState, TaskStatus};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Create the `tasks` and `push_notifications` tables (and the session-id
+/// index) if this is a fresh database.
+pub fn init(conn: &Connection) -> A2AResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tasks (
+            id TEXT PRIMARY KEY,
+            session_id TEXT,
+            status_json TEXT NOT NULL,
+            artifacts_json TEXT,
+            history_json TEXT,
+            metadata_json TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_tasks_session_id ON tasks (session_id);
+        CREATE TABLE IF NOT EXISTS push_notifications (
+            task_id TEXT PRIMARY KEY,
+            config_json TEXT NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+fn row_to_task(
+    id: String,
+    session_id: Option<String>,
+    status_json: String,
+    artifacts_json: Option<String>,
+    history_json: Option<String>,
+    metadata_json: Option<String>,
+) -> A2AResult<Task> {
+    Ok(Task {
+        id,
+        session_id,
+        status: serde_json::from_str(&status_json)?,
+        artifacts: artifacts_json
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?,
+        history: history_json
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?,
+        metadata: metadata_json
+            .map(|json| serde_json::from_str(&json))
+            .transpose()?,
+    })
+}
+
+pub fn insert_task(conn: &Connection, task: &Task) -> A2AResult<()> {
+    let status_json = serde_json::to_string(&task.status)?;
+    let artifacts_json = task
+        .artifacts
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    let history_json = task
+        .history
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    let metadata_json = task
+        .metadata
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+
+    conn.execute(
+        "INSERT INTO tasks (id, session_id, status_json, artifacts_json, history_json, metadata_json)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(id) DO UPDATE SET
+            session_id = excluded.session_id,
+            status_json = excluded.status_json,
+            artifacts_json = excluded.artifacts_json,
+            history_json = excluded.history_json,
+            metadata_json = excluded.metadata_json",
+        params![task.id, task.session_id, status_json, artifacts_json, history_json, metadata_json],
+    )?;
+    Ok(())
+}
+
+pub fn get_task(conn: &Connection, id: &str) -> A2AResult<Task> {
+    conn.query_row(
+        "SELECT id, session_id, status_json, artifacts_json, history_json, metadata_json
+         FROM tasks WHERE id = ?1",
+        params![id],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        },
+    )
+    .optional()?
+    .ok_or_else(|| A2AError::TaskNotFound(id.to_string()))
+    .and_then(
+        |(id, session_id, status_json, artifacts_json, history_json, metadata_json)| {
+            row_to_task(
+                id,
+                session_id,
+                status_json,
+                artifacts_json,
+                history_json,
+                metadata_json,
+            )
+        },
+    )
+}
+
+pub fn update_status(conn: &Connection, id: &str, status: &TaskStatus) -> A2AResult<Task> {
+    // Make sure the task exists before touching it so we report
+    // `TaskNotFound` instead of silently updating zero rows.
+    let mut task = get_task(conn, id)?;
+
+    if task.status.state.is_terminal() {
+        // Already finished (e.g. canceled while still queued); don't let a
+        // stale in-flight agent or runner resurrect it.
+        return Ok(task);
+    }
+
+    if let Some(message) = &status.message {
+        task.history.get_or_insert_with(Vec::new).push(message.clone());
+    }
+
+    let status_json = serde_json::to_string(status)?;
+    let history_json = task
+        .history
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    conn.execute(
+        "UPDATE tasks SET status_json = ?1, history_json = ?2 WHERE id = ?3",
+        params![status_json, history_json, id],
+    )?;
+    task.status = status.clone();
+    Ok(task)
+}
+
+pub fn add_artifact(conn: &Connection, id: &str, artifact: &Artifact) -> A2AResult<Task> {
+    let mut task = get_task(conn, id)?;
+    let artifacts = task.artifacts.get_or_insert_with(Vec::new);
+    artifacts.push(artifact.clone());
+    let artifacts_json = serde_json::to_string(&task.artifacts)?;
+    conn.execute(
+        "UPDATE tasks SET artifacts_json = ?1 WHERE id = ?2",
+        params![artifacts_json, id],
+    )?;
+    Ok(task)
+}
+
+pub fn cancel_task(conn: &Connection, id: &str) -> A2AResult<Task> {
+    let task = get_task(conn, id)?;
+    match task.status.state {
+        TaskState::Submitted | TaskState::Working | TaskState::InputRequired => {
+            let status = TaskStatus {
+                state: TaskState::Canceled,
+                message: task.status.message.clone(),
+                timestamp: chrono::Utc::now(),
+            };
+            update_status(conn, id, &status)
+        }
+        _ => Err(A2AError::TaskNotCancelable(format!(
+            "Task {} cannot be canceled in state {:?}",
+            id, task.status.state
+        ))),
+    }
+}
+
+pub fn set_push_notification_config(
+    conn: &Connection,
+    task_id: &str,
+    config: &PushNotificationConfig,
+) -> A2AResult<()> {
+    let config_json = serde_json::to_string(config)?;
+    conn.execute(
+        "INSERT INTO push_notifications (task_id, config_json)
+         VALUES (?1, ?2)
+         ON CONFLICT(task_id) DO UPDATE SET config_json = excluded.config_json",
+        params![task_id, config_json],
+    )?;
+    Ok(())
+}
+
+pub fn get_push_notification_config(
+    conn: &Connection,
+    task_id: &str,
+) -> A2AResult<Option<PushNotificationConfig>> {
+    conn.query_row(
+        "SELECT config_json FROM push_notifications WHERE task_id = ?1",
+        params![task_id],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()?
+    .map(|json| serde_json::from_str(&json).map_err(A2AError::from))
+    .transpose()
+}
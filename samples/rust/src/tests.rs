@@ -74,6 +74,7 @@ mod tests {
                 timestamp: Utc::now(),
             },
             artifacts: None,
+            history: None,
             metadata: None,
         };
 
@@ -98,14 +99,14 @@ mod tests {
 
 #[cfg(test)]
 mod store_tests {
-    use crate::store::TaskStore;
+    use crate::store::{MemoryTaskStore, TaskStore};
     use crate::types::{Message, Part, Task, TaskState, TaskStatus, TextPart};
     use chrono::Utc;
     use uuid::Uuid;
 
     #[test]
     fn test_task_store_create_get() {
-        let store = TaskStore::new();
+        let store = MemoryTaskStore::new();
         let task_id = Uuid::new_v4().to_string();
 
         let task = Task {
@@ -117,6 +118,7 @@ mod store_tests {
                 timestamp: Utc::now(),
             },
             artifacts: None,
+            history: None,
             metadata: None,
         };
 
@@ -132,7 +134,7 @@ mod store_tests {
 
     #[test]
     fn test_task_store_update_status() {
-        let store = TaskStore::new();
+        let store = MemoryTaskStore::new();
         let task_id = Uuid::new_v4().to_string();
 
         let task = Task {
@@ -144,6 +146,7 @@ mod store_tests {
                 timestamp: Utc::now(),
             },
             artifacts: None,
+            history: None,
             metadata: None,
         };
 
@@ -186,7 +189,7 @@ mod store_tests {
 
     #[test]
     fn test_task_store_cancel() {
-        let store = TaskStore::new();
+        let store = MemoryTaskStore::new();
         let task_id = Uuid::new_v4().to_string();
 
         let task = Task {
@@ -198,6 +201,7 @@ mod store_tests {
                 timestamp: Utc::now(),
             },
             artifacts: None,
+            history: None,
             metadata: None,
         };
 
@@ -213,6 +217,205 @@ mod store_tests {
         let retrieved_task = store.get_task(&task_id).unwrap();
         assert_eq!(retrieved_task.status.state, TaskState::Canceled);
     }
+
+    #[test]
+    fn test_update_status_after_cancel_does_not_resurrect_task() {
+        let store = MemoryTaskStore::new();
+        let task_id = Uuid::new_v4().to_string();
+
+        let task = Task {
+            id: task_id.clone(),
+            session_id: None,
+            status: TaskStatus {
+                state: TaskState::Submitted,
+                message: None,
+                timestamp: Utc::now(),
+            },
+            artifacts: None,
+            history: None,
+            metadata: None,
+        };
+
+        store.create_task(task).unwrap();
+
+        // Canceled while still queued, before a worker ever picks it up.
+        store.cancel_task(&task_id).unwrap();
+
+        // A worker dequeues the stale job anyway and tries to mark it
+        // Working, same as the first thing an agent's `handle` does; the
+        // store must not let this resurrect an already-terminal task.
+        let resurrected = store
+            .update_task_status(
+                &task_id,
+                TaskStatus {
+                    state: TaskState::Working,
+                    message: None,
+                    timestamp: Utc::now(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(resurrected.status.state, TaskState::Canceled);
+        assert_eq!(
+            store.get_task(&task_id).unwrap().status.state,
+            TaskState::Canceled
+        );
+    }
+}
+
+// Same coverage as `store_tests`, against the SQLite backend instead of the
+// in-memory one (in-memory SQLite, so these don't touch disk).
+#[cfg(test)]
+mod sqlite_store_tests {
+    use crate::store::{SqliteTaskStore, TaskStore};
+    use crate::types::{Message, Part, Task, TaskState, TaskStatus, TextPart};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_sqlite_store_create_get() {
+        let store = SqliteTaskStore::in_memory().unwrap();
+        let task_id = Uuid::new_v4().to_string();
+
+        let task = Task {
+            id: task_id.clone(),
+            session_id: Some(Uuid::new_v4().to_string()),
+            status: TaskStatus {
+                state: TaskState::Submitted,
+                message: None,
+                timestamp: Utc::now(),
+            },
+            artifacts: None,
+            history: None,
+            metadata: None,
+        };
+
+        let created_task = store.create_task(task.clone()).unwrap();
+        assert_eq!(created_task.id, task_id);
+
+        let retrieved_task = store.get_task(&task_id).unwrap();
+        assert_eq!(retrieved_task.id, task_id);
+        assert_eq!(retrieved_task.status.state, TaskState::Submitted);
+    }
+
+    #[test]
+    fn test_sqlite_store_update_status() {
+        let store = SqliteTaskStore::in_memory().unwrap();
+        let task_id = Uuid::new_v4().to_string();
+
+        let task = Task {
+            id: task_id.clone(),
+            session_id: Some(Uuid::new_v4().to_string()),
+            status: TaskStatus {
+                state: TaskState::Submitted,
+                message: None,
+                timestamp: Utc::now(),
+            },
+            artifacts: None,
+            history: None,
+            metadata: None,
+        };
+
+        store.create_task(task.clone()).unwrap();
+
+        let new_status = TaskStatus {
+            state: TaskState::Working,
+            message: Some(Message {
+                role: "agent".to_string(),
+                parts: vec![Part::Text(TextPart {
+                    part_type: "text".to_string(),
+                    text: "Working on it...".to_string(),
+                    metadata: None,
+                })],
+                metadata: None,
+            }),
+            timestamp: Utc::now(),
+        };
+
+        let updated_task = store.update_task_status(&task_id, new_status).unwrap();
+        assert_eq!(updated_task.id, task_id);
+        assert_eq!(updated_task.status.state, TaskState::Working);
+
+        let retrieved_task = store.get_task(&task_id).unwrap();
+        assert_eq!(retrieved_task.status.state, TaskState::Working);
+
+        if let Some(message) = retrieved_task.status.message {
+            if let Part::Text(text_part) = &message.parts[0] {
+                assert_eq!(text_part.text, "Working on it...");
+            } else {
+                panic!("Retrieved part is not a TextPart");
+            }
+        } else {
+            panic!("Retrieved message is None");
+        }
+    }
+
+    #[test]
+    fn test_sqlite_store_cancel() {
+        let store = SqliteTaskStore::in_memory().unwrap();
+        let task_id = Uuid::new_v4().to_string();
+
+        let task = Task {
+            id: task_id.clone(),
+            session_id: Some(Uuid::new_v4().to_string()),
+            status: TaskStatus {
+                state: TaskState::Working,
+                message: None,
+                timestamp: Utc::now(),
+            },
+            artifacts: None,
+            history: None,
+            metadata: None,
+        };
+
+        store.create_task(task.clone()).unwrap();
+
+        let canceled_task = store.cancel_task(&task_id).unwrap();
+        assert_eq!(canceled_task.id, task_id);
+        assert_eq!(canceled_task.status.state, TaskState::Canceled);
+
+        let retrieved_task = store.get_task(&task_id).unwrap();
+        assert_eq!(retrieved_task.status.state, TaskState::Canceled);
+    }
+
+    #[test]
+    fn test_sqlite_store_update_status_after_cancel_does_not_resurrect_task() {
+        let store = SqliteTaskStore::in_memory().unwrap();
+        let task_id = Uuid::new_v4().to_string();
+
+        let task = Task {
+            id: task_id.clone(),
+            session_id: None,
+            status: TaskStatus {
+                state: TaskState::Submitted,
+                message: None,
+                timestamp: Utc::now(),
+            },
+            artifacts: None,
+            history: None,
+            metadata: None,
+        };
+
+        store.create_task(task).unwrap();
+        store.cancel_task(&task_id).unwrap();
+
+        let resurrected = store
+            .update_task_status(
+                &task_id,
+                TaskStatus {
+                    state: TaskState::Working,
+                    message: None,
+                    timestamp: Utc::now(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(resurrected.status.state, TaskState::Canceled);
+        assert_eq!(
+            store.get_task(&task_id).unwrap().status.state,
+            TaskState::Canceled
+        );
+    }
 }
 
 // We'll implement integration tests separately in a more appropriate way
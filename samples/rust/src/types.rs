@@ -1,8 +1,31 @@
 use chrono::{DateTime, Utc};
+use serde::de::{Deserializer, Error as DeError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// A JSON-RPC request/response id: either a string or an integer. Kept
+/// untagged so it serializes as whichever the client sent and round-trips
+/// unchanged, rather than coercing everything to a string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    String(String),
+    Number(i64),
+}
+
+impl RequestId {
+    pub fn new() -> Self {
+        RequestId::String(Uuid::new_v4().to_string())
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Represents the state of a task within the A2A protocol.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -17,6 +40,16 @@ pub enum TaskState {
     Unknown,
 }
 
+impl TaskState {
+    /// Whether a task in this state will never transition again.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskState::Completed | TaskState::Canceled | TaskState::Failed | TaskState::Rejected
+        )
+    }
+}
+
 /// Represents a part of a message containing text content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextPart {
@@ -149,6 +182,11 @@ pub struct Task {
     pub status: TaskStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub artifacts: Option<Vec<Artifact>>,
+    /// Every status message the task has carried, oldest first. Callers
+    /// trim this to the last `history_length` entries via `tasks/get`;
+    /// the store itself always keeps the full log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<Message>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -173,18 +211,35 @@ pub struct TaskArtifactUpdateEvent {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// A single event sent down a `tasks/sendSubscribe` stream. Untagged so each
+/// frame serializes as the bare status or artifact event, matching what a
+/// one-shot `tasks/send` response would carry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TaskUpdate {
+    Status(TaskStatusUpdateEvent),
+    Artifact(TaskArtifactUpdateEvent),
+}
+
+impl TaskUpdate {
+    /// Whether this update marks the end of the stream.
+    pub fn is_final(&self) -> bool {
+        matches!(self, TaskUpdate::Status(event) if event.final_status)
+    }
+}
+
 /// Base JSON-RPC message structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcMessage {
     pub jsonrpc: String,
-    pub id: Option<String>,
+    pub id: Option<RequestId>,
 }
 
 impl Default for JsonRpcMessage {
     fn default() -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
         }
     }
 }
@@ -202,7 +257,7 @@ pub struct JsonRpcError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcResponse<T> {
     pub jsonrpc: String,
-    pub id: Option<String>,
+    pub id: Option<RequestId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -213,7 +268,7 @@ impl<T> Default for JsonRpcResponse<T> {
     fn default() -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             result: None,
             error: None,
         }
@@ -255,7 +310,7 @@ pub struct TaskSendParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendTaskRequest {
     pub jsonrpc: String,
-    pub id: Option<String>,
+    pub id: Option<RequestId>,
     pub method: String,
     pub params: TaskSendParams,
 }
@@ -264,7 +319,7 @@ impl Default for SendTaskRequest {
     fn default() -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/send".to_string(),
             params: TaskSendParams {
                 id: Uuid::new_v4().to_string(),
@@ -285,7 +340,7 @@ impl Default for SendTaskRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetTaskRequest {
     pub jsonrpc: String,
-    pub id: Option<String>,
+    pub id: Option<RequestId>,
     pub method: String,
     pub params: TaskQueryParams,
 }
@@ -294,7 +349,7 @@ impl Default for GetTaskRequest {
     fn default() -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/get".to_string(),
             params: TaskQueryParams {
                 id: Uuid::new_v4().to_string(),
@@ -309,7 +364,7 @@ impl Default for GetTaskRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CancelTaskRequest {
     pub jsonrpc: String,
-    pub id: Option<String>,
+    pub id: Option<RequestId>,
     pub method: String,
     pub params: TaskIdParams,
 }
@@ -318,7 +373,7 @@ impl Default for CancelTaskRequest {
     fn default() -> Self {
         Self {
             jsonrpc: "2.0".to_string(),
-            id: Some(Uuid::new_v4().to_string()),
+            id: Some(RequestId::new()),
             method: "tasks/cancel".to_string(),
             params: TaskIdParams {
                 id: Uuid::new_v4().to_string(),
@@ -327,3 +382,202 @@ impl Default for CancelTaskRequest {
         }
     }
 }
+
+/// Authentication details a push-notification receiver expects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthenticationInfo {
+    pub schemes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+}
+
+/// Where (and how) to deliver out-of-band updates for a task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushNotificationConfig {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<AuthenticationInfo>,
+}
+
+/// Parameters binding a `PushNotificationConfig` to a task id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPushNotificationConfig {
+    pub id: String,
+    pub push_notification_config: PushNotificationConfig,
+}
+
+/// Request to register (or replace) a task's push-notification webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetTaskPushNotificationRequest {
+    pub jsonrpc: String,
+    pub id: Option<RequestId>,
+    pub method: String,
+    pub params: TaskPushNotificationConfig,
+}
+
+impl Default for SetTaskPushNotificationRequest {
+    fn default() -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(RequestId::new()),
+            method: "tasks/pushNotification/set".to_string(),
+            params: TaskPushNotificationConfig {
+                id: Uuid::new_v4().to_string(),
+                push_notification_config: PushNotificationConfig {
+                    url: String::new(),
+                    token: None,
+                    authentication: None,
+                },
+            },
+        }
+    }
+}
+
+/// Request to read back a task's registered push-notification webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTaskPushNotificationRequest {
+    pub jsonrpc: String,
+    pub id: Option<RequestId>,
+    pub method: String,
+    pub params: TaskIdParams,
+}
+
+impl Default for GetTaskPushNotificationRequest {
+    fn default() -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(RequestId::new()),
+            method: "tasks/pushNotification/get".to_string(),
+            params: TaskIdParams {
+                id: Uuid::new_v4().to_string(),
+                metadata: None,
+            },
+        }
+    }
+}
+
+/// Request for an agent's capability set, without the rest of the Agent
+/// Card. Lets a client re-check after startup without refetching
+/// `/.well-known/agent.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetCapabilitiesRequest {
+    pub jsonrpc: String,
+    pub id: Option<RequestId>,
+    pub method: String,
+}
+
+impl Default for GetCapabilitiesRequest {
+    fn default() -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(RequestId::new()),
+            method: "agent/getCapabilities".to_string(),
+        }
+    }
+}
+
+/// Which optional protocol features an agent supports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCapabilities {
+    pub streaming: bool,
+    pub push_notifications: bool,
+}
+
+/// One task an agent advertises it can perform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<String>>,
+}
+
+/// The A2A protocol version this server implements, negotiated via the
+/// Agent Card so a client can bail out before sending work it knows the
+/// server can't parse.
+pub const PROTOCOL_VERSION: &str = "0.1.0";
+
+/// An agent's self-description, served from `/.well-known/agent.json` so
+/// clients can discover what it supports before sending work.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCard {
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub protocol_version: String,
+    pub url: String,
+    pub capabilities: AgentCapabilities,
+    pub skills: Vec<Skill>,
+    pub default_input_modes: Vec<String>,
+    pub default_output_modes: Vec<String>,
+}
+
+/// A single incoming JSON-RPC request, decoded once into its concrete type
+/// based on the `method` field rather than re-parsed per dispatch arm.
+#[derive(Debug, Clone)]
+pub enum A2ARequest {
+    TasksSend(SendTaskRequest),
+    TasksSendSubscribe(SendTaskRequest),
+    TasksGet(GetTaskRequest),
+    TasksCancel(CancelTaskRequest),
+    PushNotificationSet(SetTaskPushNotificationRequest),
+    PushNotificationGet(GetTaskPushNotificationRequest),
+    GetCapabilities(GetCapabilitiesRequest),
+    /// A syntactically valid JSON-RPC call for a method we don't recognize.
+    /// Keeps the request's `id` around (rather than discarding it) so a
+    /// `MethodNotFound` error for it can still echo the caller's id back.
+    Unknown { method: String, id: Option<RequestId> },
+}
+
+impl<'de> Deserialize<'de> for A2ARequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let method = value
+            .get("method")
+            .and_then(|m| m.as_str())
+            .ok_or_else(|| DeError::missing_field("method"))?
+            .to_string();
+
+        let request = match method.as_str() {
+            "tasks/send" => {
+                A2ARequest::TasksSend(serde_json::from_value(value).map_err(DeError::custom)?)
+            }
+            "tasks/sendSubscribe" => A2ARequest::TasksSendSubscribe(
+                serde_json::from_value(value).map_err(DeError::custom)?,
+            ),
+            "tasks/get" => {
+                A2ARequest::TasksGet(serde_json::from_value(value).map_err(DeError::custom)?)
+            }
+            "tasks/cancel" => {
+                A2ARequest::TasksCancel(serde_json::from_value(value).map_err(DeError::custom)?)
+            }
+            "tasks/pushNotification/set" => A2ARequest::PushNotificationSet(
+                serde_json::from_value(value).map_err(DeError::custom)?,
+            ),
+            "tasks/pushNotification/get" => A2ARequest::PushNotificationGet(
+                serde_json::from_value(value).map_err(DeError::custom)?,
+            ),
+            "agent/getCapabilities" => A2ARequest::GetCapabilities(
+                serde_json::from_value(value).map_err(DeError::custom)?,
+            ),
+            _ => {
+                let id = match value.get("id") {
+                    Some(id_value) => {
+                        serde_json::from_value(id_value.clone()).map_err(DeError::custom)?
+                    }
+                    None => None,
+                };
+                A2ARequest::Unknown { method, id }
+            }
+        };
+
+        Ok(request)
+    }
+}